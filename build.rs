@@ -25,20 +25,50 @@ fn main() {
         // Generate bindings at build time for other targets
         let bindings = builder()
             .header("ext/timelib/timelib.h")
-            .allowlist_var("TIMELIB_ZONETYPE_ID")
+            .allowlist_var("TIMELIB_ZONETYPE_.*")
             .allowlist_var("TIMELIB_NO_CLONE")
+            .allowlist_var("TIMELIB_OVERRIDE_TIME")
+            .allowlist_var("TIMELIB_UNSET")
+            .allowlist_var("TIMELIB_SPECIAL_.*")
+            .allowlist_var("TIMELIB_POSIX_TRANS_TYPE_.*")
+            .allowlist_var("TIMELIB_ERROR_.*")
+            .allowlist_var("TIMELIB_ERR_.*")
+            .allowlist_var("TIMELIB_WARN_.*")
+            .allowlist_function("timelib_add")
+            .allowlist_function("timelib_add_wall")
             .allowlist_function("timelib_builtin_db")
+            .allowlist_function("timelib_day_of_week")
+            .allowlist_function("timelib_day_of_year")
+            .allowlist_function("timelib_days_in_month")
+            .allowlist_function("timelib_diff")
+            .allowlist_function("timelib_diff_days")
             .allowlist_function("timelib_error_container_dtor")
             .allowlist_function("timelib_fill_holes")
+            .allowlist_function("timelib_get_time_zone_info")
+            .allowlist_function("timelib_iso_day_of_week")
+            .allowlist_function("timelib_isoweek_from_date")
+            .allowlist_function("timelib_parse_from_format")
+            .allowlist_function("timelib_parse_posix_str")
             .allowlist_function("timelib_parse_tzfile")
+            .allowlist_function("timelib_posix_str_dtor")
+            .allowlist_function("timelib_rel_time_dtor")
+            .allowlist_function("timelib_set_timezone_from_abbr")
+            .allowlist_function("timelib_set_timezone_from_offset")
+            .allowlist_function("timelib_strtointerval")
             .allowlist_function("timelib_strtotime")
+            .allowlist_function("timelib_sub")
+            .allowlist_function("timelib_sub_wall")
+            .allowlist_function("timelib_time_clone")
             .allowlist_function("timelib_time_ctor")
             .allowlist_function("timelib_time_dtor")
+            .allowlist_function("timelib_time_offset_dtor")
+            .allowlist_function("timelib_timezone_abbreviations_list")
+            .allowlist_function("timelib_timezone_id_from_abbr")
             .allowlist_function("timelib_tzinfo_dtor")
             .allowlist_function("timelib_unixtime2local")
             .allowlist_function("timelib_update_ts")
-            .header("shim/shim.h")
-            .allowlist_function("timelib_tz_get_wrapper_cached")
+            .allowlist_function("timelib_zoneinfo")
+            .allowlist_function("timelib_zoneinfo_dtor")
             .generate()
             .expect("failed to run bindgen");
 
@@ -72,14 +102,12 @@ fn main() {
         "pregenerated/parse_date.c",
         #[cfg(not(feature = "re2c"))]
         "pregenerated/parse_iso_intervals.c",
-        "shim/shim.c",
     ];
 
     let mut builder = cc::Build::new();
     let mut build = builder
         .files(src.iter())
         .include("ext/timelib")
-        .include("ext/hashmap.h")
         // taken from Makefile
         .flag("-Wall")
         .define("HAVE_STDINT_H", None)
@@ -1,8 +1,72 @@
 /* automatically generated by rust-bindgen 0.72.1 */
 
+pub const TIMELIB_SPECIAL_WEEKDAY: u32 = 1;
+pub const TIMELIB_SPECIAL_DAY_OF_WEEK_IN_MONTH: u32 = 2;
+pub const TIMELIB_SPECIAL_LAST_DAY_OF_WEEK_IN_MONTH: u32 = 3;
+pub const TIMELIB_SPECIAL_FIRST_DAY_OF_MONTH: u32 = 1;
+pub const TIMELIB_SPECIAL_LAST_DAY_OF_MONTH: u32 = 2;
+pub const TIMELIB_POSIX_TRANS_TYPE_JULIAN_NO_FEB29: u32 = 1;
+pub const TIMELIB_POSIX_TRANS_TYPE_JULIAN_FEB29: u32 = 2;
+pub const TIMELIB_POSIX_TRANS_TYPE_MWD: u32 = 3;
+pub const TIMELIB_ZONETYPE_NONE: u32 = 0;
+pub const TIMELIB_ZONETYPE_OFFSET: u32 = 1;
+pub const TIMELIB_ZONETYPE_ABBR: u32 = 2;
 pub const TIMELIB_ZONETYPE_ID: u32 = 3;
+pub const TIMELIB_OVERRIDE_TIME: u32 = 1;
 pub const TIMELIB_NO_CLONE: u32 = 2;
+pub const TIMELIB_UNSET: i32 = -9999999;
+pub const TIMELIB_ERROR_NO_ERROR: u32 = 0;
+pub const TIMELIB_ERROR_CANNOT_ALLOCATE: u32 = 1;
+pub const TIMELIB_ERROR_CORRUPT_TRANSITIONS_DONT_INCREASE: u32 = 2;
+pub const TIMELIB_ERROR_CORRUPT_NO_64BIT_PREAMBLE: u32 = 3;
+pub const TIMELIB_ERROR_CORRUPT_NO_ABBREVIATION: u32 = 4;
+pub const TIMELIB_ERROR_UNSUPPORTED_VERSION: u32 = 5;
+pub const TIMELIB_ERROR_NO_SUCH_TIMEZONE: u32 = 6;
+pub const TIMELIB_ERROR_SLIM_FILE: u32 = 7;
+pub const TIMELIB_ERROR_CORRUPT_POSIX_STRING: u32 = 8;
+pub const TIMELIB_ERROR_EMPTY_POSIX_STRING: u32 = 9;
+pub const TIMELIB_WARN_DOUBLE_TZ: u32 = 257;
+pub const TIMELIB_WARN_INVALID_TIME: u32 = 258;
+pub const TIMELIB_WARN_INVALID_DATE: u32 = 259;
+pub const TIMELIB_WARN_TRAILING_DATA: u32 = 282;
+pub const TIMELIB_ERR_DOUBLE_TZ: u32 = 513;
+pub const TIMELIB_ERR_TZID_NOT_FOUND: u32 = 514;
+pub const TIMELIB_ERR_DOUBLE_TIME: u32 = 515;
+pub const TIMELIB_ERR_DOUBLE_DATE: u32 = 516;
+pub const TIMELIB_ERR_UNEXPECTED_CHARACTER: u32 = 517;
+pub const TIMELIB_ERR_EMPTY_STRING: u32 = 518;
+pub const TIMELIB_ERR_UNEXPECTED_DATA: u32 = 519;
+pub const TIMELIB_ERR_NO_TEXTUAL_DAY: u32 = 520;
+pub const TIMELIB_ERR_NO_TWO_DIGIT_DAY: u32 = 521;
+pub const TIMELIB_ERR_NO_THREE_DIGIT_DAY_OF_YEAR: u32 = 522;
+pub const TIMELIB_ERR_NO_TWO_DIGIT_MONTH: u32 = 523;
+pub const TIMELIB_ERR_NO_TEXTUAL_MONTH: u32 = 524;
+pub const TIMELIB_ERR_NO_TWO_DIGIT_YEAR: u32 = 525;
+pub const TIMELIB_ERR_NO_FOUR_DIGIT_YEAR: u32 = 526;
+pub const TIMELIB_ERR_NO_TWO_DIGIT_HOUR: u32 = 527;
+pub const TIMELIB_ERR_HOUR_LARGER_THAN_12: u32 = 528;
+pub const TIMELIB_ERR_MERIDIAN_BEFORE_HOUR: u32 = 529;
+pub const TIMELIB_ERR_NO_MERIDIAN: u32 = 530;
+pub const TIMELIB_ERR_NO_TWO_DIGIT_MINUTE: u32 = 531;
+pub const TIMELIB_ERR_NO_TWO_DIGIT_SECOND: u32 = 532;
+pub const TIMELIB_ERR_NO_SIX_DIGIT_MICROSECOND: u32 = 533;
+pub const TIMELIB_ERR_NO_SEP_SYMBOL: u32 = 534;
+pub const TIMELIB_ERR_EXPECT_ESCAPED_CHAR: u32 = 535;
+pub const TIMELIB_ERR_NO_ESCAPED_CHAR: u32 = 536;
+pub const TIMELIB_ERR_WRONG_FORMAT_SEP: u32 = 537;
+pub const TIMELIB_ERR_TRAILING_DATA: u32 = 538;
+pub const TIMELIB_ERR_DATA_MISSING: u32 = 539;
+pub const TIMELIB_ERR_NO_THREE_DIGIT_MILLISECOND: u32 = 540;
+pub const TIMELIB_ERR_NO_FOUR_DIGIT_YEAR_ISO: u32 = 541;
+pub const TIMELIB_ERR_NO_TWO_DIGIT_WEEK: u32 = 542;
+pub const TIMELIB_ERR_INVALID_WEEK: u32 = 543;
+pub const TIMELIB_ERR_INVALID_SPECIFIER: u32 = 544;
+pub const TIMELIB_ERR_INVALID_TZ_OFFSET: u32 = 545;
+pub const TIMELIB_ERR_FORMAT_LITERAL_MISMATCH: u32 = 546;
+pub const TIMELIB_ERR_MIX_ISO_WITH_NATURAL: u32 = 547;
+pub const TIMELIB_ERR_NUMBER_OUT_OF_RANGE: u32 = 548;
 pub type timelib_sll = ::std::os::raw::c_longlong;
+pub type timelib_long = ::std::os::raw::c_long;
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct _ttinfo {
@@ -309,6 +373,31 @@ const _: () = {
 pub type timelib_rel_time = _timelib_rel_time;
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+pub struct _timelib_time_offset {
+    pub offset: i32,
+    pub leap_secs: ::std::os::raw::c_uint,
+    pub is_dst: ::std::os::raw::c_uint,
+    pub abbr: *mut ::std::os::raw::c_char,
+    pub transition_time: timelib_sll,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of _timelib_time_offset"][::std::mem::size_of::<_timelib_time_offset>() - 32usize];
+    ["Alignment of _timelib_time_offset"][::std::mem::align_of::<_timelib_time_offset>() - 8usize];
+    ["Offset of field: _timelib_time_offset::offset"]
+        [::std::mem::offset_of!(_timelib_time_offset, offset) - 0usize];
+    ["Offset of field: _timelib_time_offset::leap_secs"]
+        [::std::mem::offset_of!(_timelib_time_offset, leap_secs) - 4usize];
+    ["Offset of field: _timelib_time_offset::is_dst"]
+        [::std::mem::offset_of!(_timelib_time_offset, is_dst) - 8usize];
+    ["Offset of field: _timelib_time_offset::abbr"]
+        [::std::mem::offset_of!(_timelib_time_offset, abbr) - 16usize];
+    ["Offset of field: _timelib_time_offset::transition_time"]
+        [::std::mem::offset_of!(_timelib_time_offset, transition_time) - 24usize];
+};
+pub type timelib_time_offset = _timelib_time_offset;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
 pub struct _timelib_time {
     pub y: timelib_sll,
     pub m: timelib_sll,
@@ -375,6 +464,25 @@ const _: () = {
 pub type timelib_time = _timelib_time;
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+pub struct _timelib_abbr_info {
+    pub utc_offset: timelib_sll,
+    pub abbr: *mut ::std::os::raw::c_char,
+    pub dst: ::std::os::raw::c_int,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of _timelib_abbr_info"][::std::mem::size_of::<_timelib_abbr_info>() - 24usize];
+    ["Alignment of _timelib_abbr_info"][::std::mem::align_of::<_timelib_abbr_info>() - 8usize];
+    ["Offset of field: _timelib_abbr_info::utc_offset"]
+        [::std::mem::offset_of!(_timelib_abbr_info, utc_offset) - 0usize];
+    ["Offset of field: _timelib_abbr_info::abbr"]
+        [::std::mem::offset_of!(_timelib_abbr_info, abbr) - 8usize];
+    ["Offset of field: _timelib_abbr_info::dst"]
+        [::std::mem::offset_of!(_timelib_abbr_info, dst) - 16usize];
+};
+pub type timelib_abbr_info = _timelib_abbr_info;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
 pub struct _timelib_error_message {
     pub error_code: ::std::os::raw::c_int,
     pub position: ::std::os::raw::c_int,
@@ -422,6 +530,29 @@ const _: () = {
 pub type timelib_error_container = _timelib_error_container;
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+pub struct _timelib_tz_lookup_table {
+    pub name: *const ::std::os::raw::c_char,
+    pub type_: ::std::os::raw::c_int,
+    pub gmtoffset: f32,
+    pub full_tz_name: *const ::std::os::raw::c_char,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of _timelib_tz_lookup_table"][::std::mem::size_of::<_timelib_tz_lookup_table>() - 24usize];
+    ["Alignment of _timelib_tz_lookup_table"]
+        [::std::mem::align_of::<_timelib_tz_lookup_table>() - 8usize];
+    ["Offset of field: _timelib_tz_lookup_table::name"]
+        [::std::mem::offset_of!(_timelib_tz_lookup_table, name) - 0usize];
+    ["Offset of field: _timelib_tz_lookup_table::type_"]
+        [::std::mem::offset_of!(_timelib_tz_lookup_table, type_) - 8usize];
+    ["Offset of field: _timelib_tz_lookup_table::gmtoffset"]
+        [::std::mem::offset_of!(_timelib_tz_lookup_table, gmtoffset) - 12usize];
+    ["Offset of field: _timelib_tz_lookup_table::full_tz_name"]
+        [::std::mem::offset_of!(_timelib_tz_lookup_table, full_tz_name) - 16usize];
+};
+pub type timelib_tz_lookup_table = _timelib_tz_lookup_table;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
 pub struct _timelib_tzdb_index_entry {
     pub id: *mut ::std::os::raw::c_char,
     pub pos: ::std::os::raw::c_uint,
@@ -466,6 +597,32 @@ pub type timelib_tz_get_wrapper = ::std::option::Option<
         error_code: *mut ::std::os::raw::c_int,
     ) -> *mut timelib_tzinfo,
 >;
+unsafe extern "C" {
+    #[doc = " Calculates the day of the week from y, m, and d. 0=Sunday..6=Saturday"]
+    pub fn timelib_day_of_week(y: timelib_sll, m: timelib_sll, d: timelib_sll) -> timelib_sll;
+}
+unsafe extern "C" {
+    #[doc = " Calculates the day of the ISO week from y, m, and d. 1=Monday, 7=Sunday"]
+    pub fn timelib_iso_day_of_week(y: timelib_sll, m: timelib_sll, d: timelib_sll) -> timelib_sll;
+}
+unsafe extern "C" {
+    #[doc = " Calculates the day of the year according to y-m-d. 0=Jan 1st..364/365=Dec 31st"]
+    pub fn timelib_day_of_year(y: timelib_sll, m: timelib_sll, d: timelib_sll) -> timelib_sll;
+}
+unsafe extern "C" {
+    #[doc = " Calculates the number of days in month m for year y. 28..31"]
+    pub fn timelib_days_in_month(y: timelib_sll, m: timelib_sll) -> timelib_sll;
+}
+unsafe extern "C" {
+    #[doc = " Calculates the ISO year and week from y, m, and d, into iw and iy."]
+    pub fn timelib_isoweek_from_date(
+        y: timelib_sll,
+        m: timelib_sll,
+        d: timelib_sll,
+        iw: *mut timelib_sll,
+        iy: *mut timelib_sll,
+    );
+}
 unsafe extern "C" {
     pub fn timelib_strtotime(
         s: *const ::std::os::raw::c_char,
@@ -475,6 +632,17 @@ unsafe extern "C" {
         tz_get_wrapper: timelib_tz_get_wrapper,
     ) -> *mut timelib_time;
 }
+unsafe extern "C" {
+    #[doc = " Parses the date/time string in 's' with length 'len' into the constituent\n parts of timelib_time*, according to the format in 'format'.\n\n Depending on the contents of the string 's', not all elements might be\n filled. You can check whether a specific element has been parsed by\n comparing with the TIMELIB_UNSET define.\n\n If errors occur, this function keeps already parsed elements in the\n returned timelib_time* value.\n\n If the **errors points to a timelib_error_container variable, warnings\n and errors will be stored within this variable. You must free this\n variable with timelib_error_container_dtor()."]
+    pub fn timelib_parse_from_format(
+        format: *const ::std::os::raw::c_char,
+        s: *const ::std::os::raw::c_char,
+        len: usize,
+        errors: *mut *mut timelib_error_container,
+        tzdb: *const timelib_tzdb,
+        tz_get_wrapper: timelib_tz_get_wrapper,
+    ) -> *mut timelib_time;
+}
 unsafe extern "C" {
     pub fn timelib_fill_holes(
         parsed: *mut timelib_time,
@@ -482,6 +650,18 @@ unsafe extern "C" {
         options: ::std::os::raw::c_int,
     );
 }
+unsafe extern "C" {
+    #[doc = " Parses the ISO 8601 interval string 's' with length 'len' into its start\n and end times, its period and its number of recurrences.\n\n Only the elements present in 's' are set; the returned timelib_time and\n timelib_rel_time structures must be freed with timelib_time_dtor() and\n timelib_rel_time_dtor(), and 'errors' with timelib_error_container_dtor()."]
+    pub fn timelib_strtointerval(
+        s: *const ::std::os::raw::c_char,
+        len: usize,
+        begin: *mut *mut timelib_time,
+        end: *mut *mut timelib_time,
+        period: *mut *mut timelib_rel_time,
+        recurrences: *mut ::std::os::raw::c_int,
+        errors: *mut *mut timelib_error_container,
+    );
+}
 unsafe extern "C" {
     #[doc = " Uses the y/m/d/h/i/s fields to calculate and store the equivalent timestamp\n in the sse field.\n\n It uses the time zone information associated with 'time' to account for the\n right UTC offset and/or DST rules. You can associate time zone information\n with the timelib_set_timezone_* functions (see below).\n\n If the type is 'TIMELIB_ZONETYPE_ID' and there is no associated tzinfo, it\n will use the second argument 'tzi' to provide the rules necessary to\n calculate the right timestamp."]
     pub fn timelib_update_ts(time: *mut timelib_time, tzi: *mut timelib_tzinfo);
@@ -490,6 +670,14 @@ unsafe extern "C" {
     #[doc = " Takes the Unix timestamp from 'ts', and calculates the y/m/d/h/i/s fields\n according to the time zone information attached to 'tm'."]
     pub fn timelib_unixtime2local(tm: *mut timelib_time, ts: timelib_sll);
 }
+unsafe extern "C" {
+    #[doc = " Attaches the UTC offset as time zone information to 't'.\n\n 'utc_offset' is in seconds East of UTC."]
+    pub fn timelib_set_timezone_from_offset(t: *mut timelib_time, utc_offset: timelib_sll);
+}
+unsafe extern "C" {
+    #[doc = " Attaches the information from 'abbr_info' as time zone information to 't'.\n\n The timelib_abbr_info struct contains an abbreviation ('abbr') which string\n value is duplicated, as well as a 'utc_offset' and 'dst' flag. It only\n supports a 'dst' change over of 1 hour."]
+    pub fn timelib_set_timezone_from_abbr(t: *mut timelib_time, abbr_info: timelib_abbr_info);
+}
 unsafe extern "C" {
     #[doc = " Converts the binary stored time zone information from 'tzdb' for the time\n zone 'timezone' into a structure the library can use for calculations.\n\n The function can be used on both timelib_builtin_db as well as a time zone\n db as opened by timelib_zoneinfo.\n\n 'error_code' must not be a null pointer, and will always be written to. If\n the value is TIMELIB_ERROR_NO_ERROR then the file was parsed without\n problems.\n\n The function will return null upon failure, and also set an error code\n through 'error_code'.\n\n The error code is one of the TIMELIB_ERROR_* constants as listed above.\n These error constants can be converted into a string by\n timelib_get_error_message.\n\n If the function returns not-null, the 'error_code' might have a non-null\n value that can be used to detect incompatibilities. The only one that is\n currently detected is whether the file is a 'slim' file, in which case\n 'error_code' will be set to TIMELIB_ERROR_SLIM_FILE.\n\n This function allocates memory for the new time zone structure, which must\n be freed after use. Although it is recommended that a cache of each used\n time zone is kept."]
     pub fn timelib_parse_tzfile(
@@ -502,27 +690,96 @@ unsafe extern "C" {
     #[doc = " Frees up the resources allocated by 'timelib_parse_tzfile'."]
     pub fn timelib_tzinfo_dtor(tz: *mut timelib_tzinfo);
 }
+unsafe extern "C" {
+    #[doc = " Parses the POSIX TZ string 'posix' into a structure the library can use to\n calculate transitions and offsets. Returns NULL if the string could not be\n parsed.\n\n The returned structure must be freed with 'timelib_posix_str_dtor'."]
+    pub fn timelib_parse_posix_str(posix: *const ::std::os::raw::c_char) -> *mut timelib_posix_str;
+}
+unsafe extern "C" {
+    #[doc = " Frees up the resources allocated by 'timelib_parse_posix_str'."]
+    pub fn timelib_posix_str_dtor(ps: *mut timelib_posix_str);
+}
+unsafe extern "C" {
+    #[doc = " Returns offset information with time zone 'tz' for the time stamp 'ts'.\n\n The returned information contains: the offset in seconds East of UTC (in\n 'offset'), whether DST is active ('is_dst'), what the current time zone\n abbreviation is ('abbr') and the transition time that got to this state (in\n 'transition_time');"]
+    pub fn timelib_get_time_zone_info(
+        ts: timelib_sll,
+        tz: *mut timelib_tzinfo,
+    ) -> *mut timelib_time_offset;
+}
+unsafe extern "C" {
+    #[doc = " Returns the Olson time zone identifier that matches the abbreviation 'abbr',\n the UTC offset 'gmtoffset' (in seconds) and the DST flag 'isdst'. Pass -1 as\n 'gmtoffset' to match on the abbreviation alone.\n\n Returns NULL if no match could be found."]
+    pub fn timelib_timezone_id_from_abbr(
+        abbr: *const ::std::os::raw::c_char,
+        gmtoffset: timelib_long,
+        isdst: ::std::os::raw::c_int,
+    ) -> *const ::std::os::raw::c_char;
+}
+unsafe extern "C" {
+    #[doc = " Returns an array of known time zone abbreviations.\n\n This file is generated from the time zone database through the\n gettzmapping.php scripts, which requires that an up-to-date time zone\n database is used with the PHP binary that runs the script.\n\n Each item in the returned list contains the abbreviation, a flag whether\n it's an abbreviation used with DST, the UTC offset in seconds, and the name\n of the time zone which uses this abbreviation."]
+    pub fn timelib_timezone_abbreviations_list() -> *const timelib_tz_lookup_table;
+}
 unsafe extern "C" {
     #[doc = " Returns a pointer to the built-in time zone database.\n\n You must *not* free the returned pointer as it is part of the text segment."]
     pub fn timelib_builtin_db() -> *const timelib_tzdb;
 }
+unsafe extern "C" {
+    #[doc = " Scans the directory and subdirectories of 'directory' for valid time\n zone files and builds a time zone database out of these files.\n\n Typically, the directory should point to '/usr/share/zoneinfo'.\n\n Unlike 'timelib_builtin_db', the return value of this function must be\n freed with the 'timelib_zoneinfo_dtor' function."]
+    pub fn timelib_zoneinfo(directory: *const ::std::os::raw::c_char) -> *mut timelib_tzdb;
+}
+unsafe extern "C" {
+    #[doc = " Frees up the resources as created through 'timelib_zoneinfo'.\n\n This function must be used to free up all the resources that have been\n allocated while calling 'timelib_zoneinfo'."]
+    pub fn timelib_zoneinfo_dtor(tzdb: *mut timelib_tzdb);
+}
 unsafe extern "C" {
     #[doc = " Allocates resources for the time structure.\n\n Must be freed with 'timelib_time_dtor'."]
     pub fn timelib_time_ctor() -> *mut timelib_time;
 }
+unsafe extern "C" {
+    #[doc = " Creates a new timelib_time structure and copies all the fields from 'orig'\n to it, including the time zone information.\n\n The resulting structure must be freed with 'timelib_time_dtor'."]
+    pub fn timelib_time_clone(orig: *mut timelib_time) -> *mut timelib_time;
+}
 unsafe extern "C" {
     #[doc = " Frees up the resources as allocated through 'timelib_time_ctor'."]
     pub fn timelib_time_dtor(t: *mut timelib_time);
 }
+unsafe extern "C" {
+    #[doc = " Frees up the resources as allocated through 'timelib_rel_time_ctor'."]
+    pub fn timelib_rel_time_dtor(t: *mut timelib_rel_time);
+}
+unsafe extern "C" {
+    #[doc = " Frees up the resources as allocated through 'timelib_time_offset_ctor'."]
+    pub fn timelib_time_offset_dtor(t: *mut timelib_time_offset);
+}
 unsafe extern "C" {
     #[doc = " Frees up the resources allocated while converting strings to timelib_time\n structures with the timelib_strtotime and timelib_strtointerval functions."]
     pub fn timelib_error_container_dtor(errors: *mut timelib_error_container);
 }
 unsafe extern "C" {
-    #[doc = " A function that creates timelib_tzinfos if they don't exist and caches them in memory."]
-    pub fn timelib_tz_get_wrapper_cached(
-        tzname: *const ::std::os::raw::c_char,
-        tzdb: *const timelib_tzdb,
-        error_code: *mut ::std::os::raw::c_int,
-    ) -> *mut timelib_tzinfo;
+    #[doc = " Returns a timelib_rel_time structure that contains the difference between\n the two timelib_time structures 'one' and 'two'.\n\n The returned structure must be freed with timelib_rel_time_dtor()."]
+    pub fn timelib_diff(one: *mut timelib_time, two: *mut timelib_time) -> *mut timelib_rel_time;
+}
+unsafe extern "C" {
+    #[doc = " Returns the number of full days between 'one' and 'two', taking their local\n times into account."]
+    pub fn timelib_diff_days(one: *mut timelib_time, two: *mut timelib_time) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    #[doc = " Adds the relative time information 'interval' to the base time 't'.\n\n This can be a relative time as created by 'timelib_diff', but also by more\n complex statements such as \"next workday\"."]
+    pub fn timelib_add(t: *mut timelib_time, interval: *mut timelib_rel_time) -> *mut timelib_time;
+}
+unsafe extern "C" {
+    #[doc = " Adds the relative time information 'interval' to the base time 't', applying\n the hour, minute and second elements as elapsed time rather than wall clock\n time.\n\n This can be a relative time as created by 'timelib_diff', but also by more\n complex statements such as \"next workday\"."]
+    pub fn timelib_add_wall(
+        t: *mut timelib_time,
+        interval: *mut timelib_rel_time,
+    ) -> *mut timelib_time;
+}
+unsafe extern "C" {
+    #[doc = " Subtracts the relative time information 'interval' to the base time 't'.\n\n This can be a relative time as created by 'timelib_diff'. Unlike with\n 'timelib_add', this does not support more complex statements such as \"next\n workday\"."]
+    pub fn timelib_sub(t: *mut timelib_time, interval: *mut timelib_rel_time) -> *mut timelib_time;
+}
+unsafe extern "C" {
+    #[doc = " Subtracts the relative time information 'interval' to the base time 't',\n applying the hour, minute and second elements as elapsed time rather than\n wall clock time.\n\n This can be a relative time as created by 'timelib_diff'. Unlike with\n 'timelib_add', this does not support more complex statements such as \"next\n workday\"."]
+    pub fn timelib_sub_wall(
+        t: *mut timelib_time,
+        interval: *mut timelib_rel_time,
+    ) -> *mut timelib_time;
 }
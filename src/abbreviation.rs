@@ -0,0 +1,134 @@
+use std::ffi::{CStr, CString};
+
+use crate::internal::*;
+
+/// A timezone abbreviation from timelib's abbreviation table, along with a timezone that uses it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Abbreviation {
+    /// The abbreviation, e.g. "CEST".
+    pub abbreviation: String,
+    /// The identifier of a timezone that uses the abbreviation, e.g. "Europe/Berlin".
+    pub identifier: Option<String>,
+    /// Seconds east of UTC.
+    pub utc_offset: i32,
+    /// Whether the abbreviation denotes daylight saving time.
+    pub is_dst: bool,
+}
+
+impl Abbreviation {
+    /// Returns every entry of timelib's abbreviation table for the given abbreviation, compared
+    /// case-insensitively. An abbreviation can have several entries, e.g. "IST" is used for
+    /// India, Ireland and Israel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// for candidate in timelib::Abbreviation::lookup("IST") {
+    ///     println!("{:?} {}", candidate.identifier, candidate.utc_offset);
+    /// }
+    /// ```
+    pub fn lookup(abbreviation: &str) -> Vec<Abbreviation> {
+        Self::all()
+            .into_iter()
+            .filter(|entry| entry.abbreviation.eq_ignore_ascii_case(abbreviation))
+            .collect()
+    }
+
+    /// Returns every entry of timelib's abbreviation table.
+    pub fn all() -> Vec<Abbreviation> {
+        let mut entries = Vec::new();
+        let mut entry = unsafe { timelib_timezone_abbreviations_list() };
+        unsafe {
+            while !entry.is_null() && !(*entry).name.is_null() {
+                let raw = &*entry;
+                entries.push(Abbreviation {
+                    abbreviation: CStr::from_ptr(raw.name)
+                        .to_string_lossy()
+                        .to_ascii_uppercase(),
+                    identifier: (!raw.full_tz_name.is_null()).then(|| {
+                        CStr::from_ptr(raw.full_tz_name)
+                            .to_string_lossy()
+                            .into_owned()
+                    }),
+                    utc_offset: raw.gmtoffset as i32,
+                    is_dst: raw.type_ != 0,
+                });
+                entry = entry.add(1);
+            }
+        }
+        entries
+    }
+
+    /// Resolves an abbreviation to a timezone identifier the way timelib does: the first entry
+    /// for the abbreviation with the given UTC offset wins, then the first entry for the
+    /// abbreviation at all. Failing that, a timezone is picked solely on `utc_offset` and
+    /// `is_dst`.
+    ///
+    /// # Arguments
+    ///
+    /// * `abbreviation` - The abbreviation, e.g. "IST".
+    /// * `utc_offset` - An optional offset in seconds east of UTC to pick between candidates.
+    /// * `is_dst` - Whether daylight saving time is in effect, used when only the offset matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert_eq!(
+    ///     Some("America/New_York".to_string()),
+    ///     timelib::Abbreviation::identifier("EST", None, false)
+    /// );
+    /// ```
+    pub fn identifier(abbreviation: &str, utc_offset: Option<i32>, is_dst: bool) -> Option<String> {
+        let abbreviation = CString::new(abbreviation).ok()?;
+        let id = unsafe {
+            timelib_timezone_id_from_abbr(
+                abbreviation.as_ptr(),
+                utc_offset.map_or(-1, |offset| offset as timelib_long),
+                is_dst as i32,
+            )
+        };
+        if id.is_null() {
+            return None;
+        }
+        Some(unsafe { CStr::from_ptr(id) }.to_string_lossy().into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Timezone;
+
+    #[test]
+    fn abbreviation_lookup() {
+        let est = Abbreviation::lookup("est");
+        assert!(est.contains(&Abbreviation {
+            abbreviation: "EST".into(),
+            identifier: Some("America/New_York".into()),
+            utc_offset: -18000,
+            is_dst: false,
+        }));
+
+        let ist = Abbreviation::lookup("IST");
+        for offset in [3600, 7200, 19800] {
+            assert!(ist.iter().any(|entry| entry.utc_offset == offset));
+        }
+        assert!(Abbreviation::lookup("NOPE").is_empty());
+    }
+
+    #[test]
+    fn abbreviation_identifier() {
+        assert_eq!(
+            Some("Europe/Berlin".to_string()),
+            Abbreviation::identifier("CEST", None, true)
+        );
+        let india = Abbreviation::identifier("IST", Some(19800), false).unwrap();
+        let tz = Timezone::parse(&india).unwrap();
+        assert_eq!(19800, tz.offset_at(1654318800).utc_offset);
+        assert_eq!(
+            Some("Europe/Paris".to_string()),
+            Abbreviation::identifier("", Some(3600), false)
+        );
+        assert_eq!(None, Abbreviation::identifier("NOPE", None, false));
+    }
+}
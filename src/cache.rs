@@ -0,0 +1,328 @@
+//! The cache of timezones that timelib looks up while parsing date/time strings, such as the
+//! "America/New_York" in "2006-05-12 13:00:00 America/New_York".
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    ffi::{c_char, c_int, CStr},
+    ptr,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use crate::{internal::*, timezone::TzInfo};
+
+/// Statistics about the timezone cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// The number of lookups served from the cache.
+    pub hits: u64,
+    /// The number of lookups that had to parse the timezone.
+    pub misses: u64,
+    /// The number of timezones evicted to stay within the capacity.
+    pub evictions: u64,
+    /// The number of timezones currently cached.
+    pub entries: usize,
+}
+
+/// Sets the maximum number of timezones cached while parsing date/time strings, evicting the
+/// least recently used ones if the cache is currently larger. `None` (the default) means the
+/// cache is unbounded.
+///
+/// # Examples
+///
+/// ```
+/// timelib::set_cache_capacity(Some(64));
+/// let stats = timelib::cache_stats();
+/// println!("{} hits, {} misses", stats.hits, stats.misses);
+/// timelib::clear_cache();
+/// ```
+pub fn set_cache_capacity(capacity: Option<usize>) {
+    with_cache(|cache| cache.set_capacity(capacity));
+}
+
+/// Returns the maximum number of cached timezones, or `None` if the cache is unbounded.
+pub fn cache_capacity() -> Option<usize> {
+    with_cache(|cache| cache.capacity)
+}
+
+/// Removes all timezones from the cache. The statistics counters are left untouched.
+pub fn clear_cache() {
+    with_cache(|cache| cache.entries.clear());
+}
+
+/// Returns the timezone cache's hit, miss and eviction counters along with its current size.
+pub fn cache_stats() -> CacheStats {
+    with_cache(|cache| cache.stats())
+}
+
+/// Identifies a cached timezone by the address of its database and its name.
+type CacheKey = (usize, Vec<u8>);
+
+struct Entry {
+    tz: Arc<TzInfo>,
+    last_used: u64,
+}
+
+/// A timezone cache with least-recently-used eviction.
+#[derive(Default)]
+struct TzCache {
+    entries: HashMap<CacheKey, Entry>,
+    capacity: Option<usize>,
+    clock: u64,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+impl TzCache {
+    fn get(&mut self, key: &CacheKey) -> Option<Arc<TzInfo>> {
+        self.clock += 1;
+        match self.entries.get_mut(key) {
+            Some(entry) => {
+                self.hits += 1;
+                entry.last_used = self.clock;
+                Some(Arc::clone(&entry.tz))
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Caches a timezone and returns it, unless the key is cached already, in which case the
+    /// existing timezone is kept and returned instead.
+    fn insert(&mut self, key: CacheKey, tz: Arc<TzInfo>) -> Arc<TzInfo> {
+        if self.capacity == Some(0) {
+            return tz;
+        }
+        let entry = self.entries.entry(key).or_insert(Entry {
+            tz,
+            last_used: self.clock,
+        });
+        let tz = Arc::clone(&entry.tz);
+        self.evict();
+        tz
+    }
+
+    fn set_capacity(&mut self, capacity: Option<usize>) {
+        self.capacity = capacity;
+        self.evict();
+    }
+
+    /// Evicts the least recently used entries until the cache fits its capacity.
+    fn evict(&mut self) {
+        let Some(capacity) = self.capacity else {
+            return;
+        };
+        while self.entries.len() > capacity {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            self.entries.remove(&oldest);
+            self.evictions += 1;
+        }
+    }
+
+    fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            evictions: self.evictions,
+            entries: self.entries.len(),
+        }
+    }
+}
+
+/// Timezones parsed while evaluating date/time strings.
+static CACHE: Mutex<Option<TzCache>> = Mutex::new(None);
+
+fn with_cache<T>(f: impl FnOnce(&mut TzCache) -> T) -> T {
+    let mut cache = CACHE.lock().unwrap_or_else(PoisonError::into_inner);
+    f(cache.get_or_insert_with(TzCache::default))
+}
+
+/// Removes the timezones loaded from the given database, which is about to be freed.
+pub(crate) fn remove_db(tzdb: *const timelib_tzdb) {
+    with_cache(|cache| cache.entries.retain(|(db, _), _| *db != tzdb as usize));
+}
+
+thread_local! {
+    /// The timezones handed to timelib on this thread during the current `pinning` call.
+    static PINNED: RefCell<Vec<Arc<TzInfo>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Runs `f`, returning the timezones the cache handed to timelib in the meantime. Holding on to
+/// them keeps the `timelib_tzinfo`s referenced by a parsed time alive even if they are evicted.
+pub(crate) fn pinning<T>(f: impl FnOnce() -> T) -> (T, Vec<Arc<TzInfo>>) {
+    let previous = PINNED.with(|pinned| pinned.take());
+    let result = f();
+    let pinned = PINNED.with(|pinned| pinned.replace(previous));
+    (result, pinned)
+}
+
+/// A `timelib_tz_get_wrapper` that creates timelib_tzinfos if they don't exist and caches them
+/// in memory. Safe to call from multiple threads at once.
+pub(crate) unsafe extern "C" fn tz_get_wrapper_cached(
+    tzname: *const c_char,
+    tzdb: *const timelib_tzdb,
+    error_code: *mut c_int,
+) -> *mut timelib_tzinfo {
+    let key = (tzdb as usize, CStr::from_ptr(tzname).to_bytes().to_vec());
+    let tz = match with_cache(|cache| cache.get(&key)) {
+        Some(existing) => {
+            *error_code = TIMELIB_ERROR_NO_ERROR as c_int;
+            Some(existing)
+        }
+        None => {
+            // Parse without holding the lock, so that other threads aren't held up by a miss.
+            let tzi = timelib_parse_tzfile(tzname, tzdb, error_code);
+            (!tzi.is_null()).then(|| {
+                // Another thread may have cached the same timezone in the meantime.
+                let tz = Arc::new(TzInfo::new(tzi));
+                with_cache(|cache| cache.insert(key, tz))
+            })
+        }
+    };
+
+    match tz {
+        Some(tz) => {
+            let tzi = tz.as_ptr();
+            PINNED.with(|pinned| pinned.borrow_mut().push(tz));
+            tzi
+        }
+        None => ptr::null_mut(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ffi::CString, thread};
+
+    use super::*;
+    use crate::{parse, strtotime, Timezone};
+
+    fn tzinfo(name: &str) -> Arc<TzInfo> {
+        let name = CString::new(name).unwrap();
+        let mut error_code = 0;
+        let tzi =
+            unsafe { timelib_parse_tzfile(name.as_ptr(), timelib_builtin_db(), &mut error_code) };
+        assert!(!tzi.is_null());
+        Arc::new(TzInfo::new(tzi))
+    }
+
+    fn key(name: &str) -> CacheKey {
+        (0, name.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn cache_concurrent_access() {
+        let inputs = [
+            ("2006-05-12 13:00:00 America/New_York", 1147453200),
+            ("2006-05-12 13:00:00 Europe/London", 1147435200),
+            ("2006-05-12 13:00:00 Asia/Tokyo", 1147406400),
+            ("2006-05-12 13:00:00 Australia/Sydney", 1147402800),
+        ];
+        let handles: Vec<_> = (0..16)
+            .map(|i| {
+                thread::spawn(move || {
+                    let tz = Timezone::parse("UTC").unwrap();
+                    for j in 0..200 {
+                        let (input, expected) = inputs[(i + j) % inputs.len()];
+                        assert_eq!(Ok(expected), strtotime(input, None, &tz));
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn cache_hits_and_misses() {
+        let mut cache = TzCache::default();
+        assert!(cache.get(&key("Europe/Paris")).is_none());
+        cache.insert(key("Europe/Paris"), tzinfo("Europe/Paris"));
+        assert!(cache.get(&key("Europe/Paris")).is_some());
+        assert!(cache.get(&key("Europe/Paris")).is_some());
+        assert_eq!(
+            CacheStats {
+                hits: 2,
+                misses: 1,
+                evictions: 0,
+                entries: 1,
+            },
+            cache.stats()
+        );
+    }
+
+    #[test]
+    fn cache_evicts_least_recently_used() {
+        let mut cache = TzCache::default();
+        cache.set_capacity(Some(2));
+        for name in ["Europe/Paris", "Europe/Berlin"] {
+            cache.get(&key(name));
+            cache.insert(key(name), tzinfo(name));
+        }
+        // Touch Paris so that Berlin becomes the least recently used entry.
+        assert!(cache.get(&key("Europe/Paris")).is_some());
+        cache.get(&key("Europe/Rome"));
+        cache.insert(key("Europe/Rome"), tzinfo("Europe/Rome"));
+
+        assert!(cache.get(&key("Europe/Paris")).is_some());
+        assert!(cache.get(&key("Europe/Rome")).is_some());
+        assert!(cache.get(&key("Europe/Berlin")).is_none());
+        assert_eq!(1, cache.stats().evictions);
+        assert_eq!(2, cache.stats().entries);
+    }
+
+    #[test]
+    fn cache_shrinking_capacity_evicts() {
+        let mut cache = TzCache::default();
+        for name in ["Europe/Paris", "Europe/Berlin", "Europe/Rome"] {
+            cache.insert(key(name), tzinfo(name));
+        }
+        cache.set_capacity(Some(1));
+        assert_eq!(1, cache.stats().entries);
+        assert_eq!(2, cache.stats().evictions);
+        cache.set_capacity(Some(0));
+        cache.insert(key("Europe/Paris"), tzinfo("Europe/Paris"));
+        assert_eq!(0, cache.stats().entries);
+    }
+
+    #[test]
+    fn cache_insert_keeps_existing() {
+        let mut cache = TzCache::default();
+        let first = cache.insert(key("Europe/Paris"), tzinfo("Europe/Paris"));
+        let second = cache.insert(key("Europe/Paris"), tzinfo("Europe/Paris"));
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(Arc::ptr_eq(
+            &first,
+            &cache.get(&key("Europe/Paris")).unwrap()
+        ));
+        assert_eq!(1, cache.stats().entries);
+    }
+
+    #[test]
+    fn cache_parsed_time_outlives_eviction() {
+        let parsed = parse("2006-05-12 13:00:00 Pacific/Auckland").unwrap();
+        clear_cache();
+        let tz = Timezone::parse("UTC").unwrap();
+        assert_eq!(Some("Pacific/Auckland".to_string()), parsed.timezone_id());
+        assert_eq!(1147395600, parsed.to_timestamp(None, &tz));
+    }
+
+    #[test]
+    fn cache_set_capacity() {
+        let previous = cache_capacity();
+        set_cache_capacity(Some(1024));
+        assert_eq!(Some(1024), cache_capacity());
+        set_cache_capacity(previous);
+    }
+}
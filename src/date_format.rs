@@ -0,0 +1,363 @@
+use std::{
+    ffi::CStr,
+    fmt::{self, Write},
+};
+
+use crate::{internal::*, Timestamp, Timezone};
+
+const DAY_NAMES: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// The local time breakdown of a timestamp that format characters are rendered from.
+struct LocalTime {
+    y: i64,
+    m: i64,
+    d: i64,
+    h: i64,
+    i: i64,
+    s: i64,
+    us: i64,
+    sse: i64,
+    /// Seconds east of UTC, including any DST hour.
+    utc_offset: i32,
+    is_dst: bool,
+    /// What "T" renders, e.g. "CDT".
+    abbreviation: String,
+    /// What "e" renders, e.g. "America/Chicago".
+    identifier: String,
+    /// Whether "p" renders "Z", which PHP only does for UTC itself rather than any zone that is
+    /// currently at offset zero, like Europe/London in winter.
+    is_utc: bool,
+}
+
+impl LocalTime {
+    fn new(timestamp: Timestamp, timezone: &Timezone) -> Self {
+        unsafe {
+            let time = timezone.local_time(timestamp.seconds);
+            let raw = &*time;
+            let (utc_offset, abbreviation, identifier) = match raw.zone_type {
+                TIMELIB_ZONETYPE_OFFSET => {
+                    let offset = format_offset(raw.z, ":");
+                    (raw.z, offset.clone(), offset)
+                }
+                TIMELIB_ZONETYPE_ABBR => {
+                    // timelib keeps the DST hour out of the offset of abbreviations.
+                    let abbreviation = CStr::from_ptr(raw.tz_abbr).to_string_lossy();
+                    (
+                        raw.z + raw.dst * 3600,
+                        abbreviation.to_string(),
+                        abbreviation.to_string(),
+                    )
+                }
+                _ => (
+                    raw.z,
+                    CStr::from_ptr(raw.tz_abbr).to_string_lossy().into_owned(),
+                    CStr::from_ptr((*raw.tz_info).name)
+                        .to_string_lossy()
+                        .into_owned(),
+                ),
+            };
+            let is_utc = match raw.zone_type {
+                TIMELIB_ZONETYPE_OFFSET => raw.z == 0,
+                _ => ["UTC", "Z"].contains(&abbreviation.as_str()),
+            };
+            let local = LocalTime {
+                y: raw.y,
+                m: raw.m,
+                d: raw.d,
+                h: raw.h,
+                i: raw.i,
+                s: raw.s,
+                us: timestamp.microseconds.into(),
+                sse: timestamp.seconds,
+                utc_offset,
+                is_dst: raw.dst != 0,
+                abbreviation,
+                identifier,
+                is_utc,
+            };
+            timelib_time_dtor(time);
+            local
+        }
+    }
+
+    /// Renders a single format character, copying unknown characters verbatim.
+    fn write(&self, out: &mut String, c: char) -> fmt::Result {
+        let (y, m, d) = (self.y, self.m, self.d);
+        match c {
+            // Day
+            'd' => write!(out, "{d:02}"),
+            'D' => out.write_str(&self.day_name()[..3]),
+            'j' => write!(out, "{d}"),
+            'l' => out.write_str(self.day_name()),
+            'N' => write!(out, "{}", unsafe { timelib_iso_day_of_week(y, m, d) }),
+            'S' => out.write_str(english_suffix(d)),
+            'w' => write!(out, "{}", unsafe { timelib_day_of_week(y, m, d) }),
+            'z' => write!(out, "{}", unsafe { timelib_day_of_year(y, m, d) }),
+            // Week
+            'W' => write!(out, "{:02}", self.iso_week().0),
+            // Month
+            'F' => out.write_str(self.month_name()),
+            'm' => write!(out, "{m:02}"),
+            'M' => out.write_str(&self.month_name()[..3]),
+            'n' => write!(out, "{m}"),
+            't' => write!(out, "{}", self.days_in_month()),
+            // Year
+            'L' => write!(
+                out,
+                "{}",
+                (unsafe { timelib_days_in_month(y, 2) } == 29) as u8
+            ),
+            'o' => write!(out, "{}", self.iso_week().1),
+            'X' => write!(
+                out,
+                "{}{:04}",
+                if y < 0 { '-' } else { '+' },
+                y.unsigned_abs()
+            ),
+            'x' if !(0..10000).contains(&y) => self.write(out, 'X'),
+            'x' | 'Y' => write!(
+                out,
+                "{}{:04}",
+                if y < 0 { "-" } else { "" },
+                y.unsigned_abs()
+            ),
+            'y' => write!(out, "{:02}", y % 100),
+            // Time
+            'a' => out.write_str(if self.h >= 12 { "pm" } else { "am" }),
+            'A' => out.write_str(if self.h >= 12 { "PM" } else { "AM" }),
+            'B' => {
+                // Swatch Internet Time, which is based on UTC+01:00.
+                let mut beats = (self.sse % 86400 + 3600) * 10;
+                if beats < 0 {
+                    beats += 864000;
+                }
+                write!(out, "{:03}", beats / 864 % 1000)
+            }
+            'g' => write!(out, "{}", self.hour12()),
+            'G' => write!(out, "{}", self.h),
+            'h' => write!(out, "{:02}", self.hour12()),
+            'H' => write!(out, "{:02}", self.h),
+            'i' => write!(out, "{:02}", self.i),
+            's' => write!(out, "{:02}", self.s),
+            'u' => write!(out, "{:06}", self.us),
+            'v' => write!(out, "{:03}", self.us / 1000),
+            // Timezone
+            'e' => out.write_str(&self.identifier),
+            'I' => write!(out, "{}", self.is_dst as u8),
+            'O' => out.write_str(&format_offset(self.utc_offset, "")),
+            'P' => out.write_str(&format_offset(self.utc_offset, ":")),
+            'p' if self.is_utc => out.write_char('Z'),
+            'p' => self.write(out, 'P'),
+            'T' => out.write_str(&self.abbreviation),
+            'Z' => write!(out, "{}", self.utc_offset),
+            // Full date/time
+            'c' => self.write_all(out, "Y-m-d\\TH:i:sP"),
+            'r' => self.write_all(out, "D, d M Y H:i:s O"),
+            'U' => write!(out, "{}", self.sse),
+            c => out.write_char(c),
+        }
+    }
+
+    /// Renders a whole format string; a backslash makes the next character literal.
+    fn write_all(&self, out: &mut String, format: &str) -> fmt::Result {
+        let mut chars = format.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => {
+                    if let Some(c) = chars.next() {
+                        out.write_char(c)?;
+                    }
+                }
+                c => self.write(out, c)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn day_name(&self) -> &'static str {
+        DAY_NAMES[unsafe { timelib_day_of_week(self.y, self.m, self.d) } as usize]
+    }
+
+    fn month_name(&self) -> &'static str {
+        MONTH_NAMES[self.m as usize - 1]
+    }
+
+    fn days_in_month(&self) -> i64 {
+        unsafe { timelib_days_in_month(self.y, self.m) }
+    }
+
+    fn hour12(&self) -> i64 {
+        match self.h % 12 {
+            0 => 12,
+            h => h,
+        }
+    }
+
+    /// Returns the ISO 8601 week number and the year it belongs to.
+    fn iso_week(&self) -> (i64, i64) {
+        let (mut week, mut year) = (0, 0);
+        unsafe { timelib_isoweek_from_date(self.y, self.m, self.d, &mut week, &mut year) };
+        (week, year)
+    }
+}
+
+/// Formats a timestamp in a timezone using PHP `date()` format characters.
+pub(crate) fn format(format: &str, timestamp: Timestamp, timezone: &Timezone) -> String {
+    let mut out = String::with_capacity(format.len() * 2);
+    LocalTime::new(timestamp, timezone)
+        .write_all(&mut out, format)
+        .expect("writing to a String cannot fail");
+    out
+}
+
+/// Returns the English ordinal suffix of a day of the month.
+fn english_suffix(day: i64) -> &'static str {
+    if (10..=19).contains(&day) {
+        return "th";
+    }
+    match day % 10 {
+        1 => "st",
+        2 => "nd",
+        3 => "rd",
+        _ => "th",
+    }
+}
+
+/// Formats an offset in seconds east of UTC as e.g. "+0530", or "+05:30" with a ":" separator.
+fn format_offset(utc_offset: i32, separator: &str) -> String {
+    let sign = if utc_offset < 0 { '-' } else { '+' };
+    let seconds = utc_offset.unsigned_abs();
+    format!(
+        "{sign}{:02}{separator}{:02}",
+        seconds / 3600,
+        seconds / 60 % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format_at(fmt: &str, seconds: i64, timezone: &Timezone) -> String {
+        format(
+            fmt,
+            Timestamp {
+                seconds,
+                microseconds: 0,
+            },
+            timezone,
+        )
+    }
+
+    #[test]
+    fn format_common_formats() {
+        let tz = Timezone::parse("America/Chicago").unwrap();
+        let today = 1654318823; // Saturday, June 4, 2022 12:00:23 AM GMT-05:00 DST
+        assert_eq!(
+            "Sat, 04 Jun 2022 00:00:23 -0500",
+            format_at("D, d M Y H:i:s O", today, &tz)
+        );
+        assert_eq!(
+            "2022-06-04T00:00:23-05:00",
+            format_at("Y-m-d\\TH:i:sP", today, &tz)
+        );
+        assert_eq!("2022-06-04T00:00:23-05:00", format_at("c", today, &tz));
+        assert_eq!(
+            "Sat, 04 Jun 2022 00:00:23 -0500",
+            format_at("r", today, &tz)
+        );
+        assert_eq!("1654318823", format_at("U", today, &tz));
+    }
+
+    #[test]
+    fn format_characters() {
+        let tz = Timezone::parse("America/Chicago").unwrap();
+        let today = 1654318823;
+        let cases = [
+            ("j S l N w z", "4 th Saturday 6 6 154"),
+            ("W o", "22 2022"),
+            ("F n t L y", "June 6 30 0 22"),
+            ("a A g G h H i s", "am AM 12 0 12 00 00 23"),
+            ("B u v", "250 000000 000"),
+            ("e T I Z p", "America/Chicago CDT 1 -18000 -05:00"),
+            ("X x", "+2022 2022"),
+            ("\\Y\\m\\d \\\\", "Ymd \\"),
+        ];
+        for (fmt, expected) in cases {
+            assert_eq!(expected, format_at(fmt, today, &tz), "format {fmt:?}");
+        }
+    }
+
+    #[test]
+    fn format_iso_week_year() {
+        let tz = Timezone::parse("UTC").unwrap();
+        // 2021-01-01 belongs to the last week of 2020.
+        assert_eq!("53 2020 Z", format_at("W o p", 1609459200, &tz));
+        // 2024-12-30 belongs to the first week of 2025.
+        assert_eq!("01 2025 1", format_at("W o N", 1735516800, &tz));
+        assert_eq!("1 31 st", format_at("L t S", 1704067200, &tz));
+    }
+
+    #[test]
+    fn format_microseconds() {
+        let tz = Timezone::parse("UTC").unwrap();
+        let timestamp = Timestamp {
+            seconds: 1704110400,
+            microseconds: 123456,
+        };
+        assert_eq!("12:00:00.123456 123", format("H:i:s.u v", timestamp, &tz));
+    }
+
+    #[test]
+    fn format_utc_designator() {
+        let january = 1704110400; // 2024-01-01 12:00:00 UTC
+        let zones = [
+            (Timezone::parse("UTC").unwrap(), "Z"),
+            (Timezone::from_offset(0), "Z"),
+            (Timezone::parse_abbreviation("UTC").unwrap(), "Z"),
+            (Timezone::parse("Europe/London").unwrap(), "+00:00"),
+            (Timezone::parse("Africa/Abidjan").unwrap(), "+00:00"),
+            (Timezone::parse_abbreviation("GMT").unwrap(), "+00:00"),
+        ];
+        for (tz, expected) in zones {
+            assert_eq!(expected, format_at("p", january, &tz), "{tz:?}");
+        }
+    }
+
+    #[test]
+    fn format_offset_and_abbreviation_zones() {
+        let ist = Timezone::parse_offset("+05:30").unwrap();
+        assert_eq!(
+            "2024-01-01 17:30 +05:30 +05:30 +0530 19800 0",
+            format_at("Y-m-d H:i e T O Z I", 1704110400, &ist)
+        );
+        let pdt = Timezone::parse_abbreviation("PDT").unwrap();
+        assert_eq!(
+            "2024-01-01 05:00 PDT PDT -07:00 1",
+            format_at("Y-m-d H:i e T P I", 1704110400, &pdt)
+        );
+    }
+}
@@ -0,0 +1,279 @@
+use std::{ffi::CStr, fmt, path::PathBuf};
+
+use crate::internal::*;
+
+/// The errors returned by this crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The input string was empty.
+    EmptyInput,
+    /// The input string contained a NUL byte at the given position.
+    InteriorNul(usize),
+    /// timelib could not parse the input string.
+    Parse(Vec<ParseError>),
+    /// timelib could not load the requested timezone.
+    Timezone(TzError),
+    /// No timezone database could be loaded from the given directory.
+    InvalidDatabase(PathBuf),
+    /// The TZif data for the named timezone is truncated or corrupt.
+    InvalidTzif(String),
+    /// Two timezones in the database have names that differ only by case, like the given one.
+    DuplicateTimezone(String),
+    /// The string is not a UTC offset such as "+05:30".
+    InvalidOffset(String),
+    /// The ISO 8601 interval holds neither a period nor a start and end.
+    InvalidInterval(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::EmptyInput => write!(f, "Empty input string"),
+            Error::InteriorNul(position) => {
+                write!(f, "Input string contains a NUL byte at position {position}")
+            }
+            Error::Parse(errors) => {
+                write!(f, "Parse error")?;
+                for (i, error) in errors.iter().enumerate() {
+                    write!(f, "{}{error}", if i == 0 { ": " } else { "; " })?;
+                }
+                Ok(())
+            }
+            Error::Timezone(error) => write!(f, "Invalid timezone: {error}"),
+            Error::InvalidDatabase(path) => write!(
+                f,
+                "No timezone database could be loaded from {}",
+                path.display()
+            ),
+            Error::InvalidTzif(name) => write!(f, "Invalid TZif data for timezone {name}"),
+            Error::DuplicateTimezone(name) => write!(f, "Duplicate timezone name: {name}"),
+            Error::InvalidOffset(offset) => write!(f, "Invalid UTC offset: {offset}"),
+            Error::InvalidInterval(interval) => write!(f, "Invalid ISO 8601 interval: {interval}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Parse(errors) => errors
+                .first()
+                .map(|e| e as &(dyn std::error::Error + 'static)),
+            Error::Timezone(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<TzError> for Error {
+    fn from(error: TzError) -> Self {
+        Error::Timezone(error)
+    }
+}
+
+/// An error reported by timelib while loading a timezone, mapped from the `TIMELIB_ERROR_*` codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TzError {
+    /// timelib could not allocate memory for the timezone.
+    CannotAllocate,
+    /// The transitions in the tzfile don't always increase.
+    CorruptTransitionsDontIncrease,
+    /// The tzfile is missing its 64-bit preamble.
+    CorruptNo64BitPreamble,
+    /// The tzfile has a transition with no abbreviation.
+    CorruptNoAbbreviation,
+    /// The tzfile uses an unsupported version.
+    UnsupportedVersion,
+    /// No timezone with this name exists in the database.
+    NoSuchTimezone,
+    /// The tzfile is a "slim" file.
+    SlimFile,
+    /// The POSIX string embedded in the tzfile is not valid.
+    CorruptPosixString,
+    /// The POSIX string embedded in the tzfile is empty.
+    EmptyPosixString,
+    /// An error code this crate does not know about.
+    Unknown(i32),
+}
+
+impl TzError {
+    /// Maps a `TIMELIB_ERROR_*` code to a `TzError`.
+    pub(crate) fn from_code(code: i32) -> Self {
+        match code as u32 {
+            TIMELIB_ERROR_CANNOT_ALLOCATE => TzError::CannotAllocate,
+            TIMELIB_ERROR_CORRUPT_TRANSITIONS_DONT_INCREASE => {
+                TzError::CorruptTransitionsDontIncrease
+            }
+            TIMELIB_ERROR_CORRUPT_NO_64BIT_PREAMBLE => TzError::CorruptNo64BitPreamble,
+            TIMELIB_ERROR_CORRUPT_NO_ABBREVIATION => TzError::CorruptNoAbbreviation,
+            TIMELIB_ERROR_UNSUPPORTED_VERSION => TzError::UnsupportedVersion,
+            TIMELIB_ERROR_NO_SUCH_TIMEZONE => TzError::NoSuchTimezone,
+            TIMELIB_ERROR_SLIM_FILE => TzError::SlimFile,
+            TIMELIB_ERROR_CORRUPT_POSIX_STRING => TzError::CorruptPosixString,
+            TIMELIB_ERROR_EMPTY_POSIX_STRING => TzError::EmptyPosixString,
+            _ => TzError::Unknown(code),
+        }
+    }
+
+    /// Returns the underlying timelib error code.
+    pub fn code(&self) -> i32 {
+        (match self {
+            TzError::CannotAllocate => TIMELIB_ERROR_CANNOT_ALLOCATE,
+            TzError::CorruptTransitionsDontIncrease => {
+                TIMELIB_ERROR_CORRUPT_TRANSITIONS_DONT_INCREASE
+            }
+            TzError::CorruptNo64BitPreamble => TIMELIB_ERROR_CORRUPT_NO_64BIT_PREAMBLE,
+            TzError::CorruptNoAbbreviation => TIMELIB_ERROR_CORRUPT_NO_ABBREVIATION,
+            TzError::UnsupportedVersion => TIMELIB_ERROR_UNSUPPORTED_VERSION,
+            TzError::NoSuchTimezone => TIMELIB_ERROR_NO_SUCH_TIMEZONE,
+            TzError::SlimFile => TIMELIB_ERROR_SLIM_FILE,
+            TzError::CorruptPosixString => TIMELIB_ERROR_CORRUPT_POSIX_STRING,
+            TzError::EmptyPosixString => TIMELIB_ERROR_EMPTY_POSIX_STRING,
+            TzError::Unknown(code) => return *code,
+        }) as i32
+    }
+}
+
+impl fmt::Display for TzError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Same wording as timelib's `timelib_get_error_message`.
+        match self {
+            TzError::CannotAllocate => write!(f, "Cannot allocate buffer for parsing"),
+            TzError::CorruptTransitionsDontIncrease => write!(
+                f,
+                "Corrupt tzfile: The transitions in the file don't always increase"
+            ),
+            TzError::CorruptNo64BitPreamble => {
+                write!(f, "Corrupt tzfile: The expected 64-bit preamble is missing")
+            }
+            TzError::CorruptNoAbbreviation => write!(
+                f,
+                "Corrupt tzfile: No abbreviation could be found for a transition"
+            ),
+            TzError::UnsupportedVersion => write!(
+                f,
+                "The version used in this timezone identifier is unsupported"
+            ),
+            TzError::NoSuchTimezone => write!(f, "No timezone with this name could be found"),
+            TzError::SlimFile => write!(f, "A 'slim' timezone file has been detected"),
+            TzError::CorruptPosixString => write!(f, "The embedded POSIX string is not valid"),
+            TzError::EmptyPosixString => write!(f, "The embedded POSIX string is empty"),
+            TzError::Unknown(code) => write!(f, "Unknown error code: {code}"),
+        }
+    }
+}
+
+impl std::error::Error for TzError {}
+
+/// An error or warning reported by timelib while parsing a date/time string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The message describing the problem.
+    pub message: String,
+    /// The byte offset into the input at which the problem was detected.
+    pub position: usize,
+    /// The input byte found at `position`.
+    pub character: u8,
+    /// The underlying timelib code (one of the `TIMELIB_ERR_*` or `TIMELIB_WARN_*` values).
+    pub code: i32,
+}
+
+impl ParseError {
+    /// Copies the given timelib messages into owned `ParseError`s.
+    pub(crate) unsafe fn from_messages(
+        messages: *const timelib_error_message,
+        count: i32,
+    ) -> Vec<Self> {
+        if messages.is_null() || count <= 0 {
+            return Vec::new();
+        }
+        std::slice::from_raw_parts(messages, count as usize)
+            .iter()
+            .map(|m| Self {
+                message: if m.message.is_null() {
+                    String::new()
+                } else {
+                    CStr::from_ptr(m.message).to_string_lossy().into_owned()
+                },
+                position: m.position.max(0) as usize,
+                character: m.character as u8,
+                code: m.error_code,
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A warning reported by timelib for input that could still be parsed, such as an invalid date
+/// that was rolled over into the next month.
+pub type ParseWarning = ParseError;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tz_error_from_code() {
+        assert_eq!(TzError::NoSuchTimezone, TzError::from_code(6));
+        assert_eq!(TzError::SlimFile, TzError::from_code(7));
+        assert_eq!(TzError::Unknown(42), TzError::from_code(42));
+    }
+
+    #[test]
+    fn tz_error_code_round_trip() {
+        for code in 1..=9 {
+            assert_eq!(code, TzError::from_code(code).code());
+        }
+        assert_eq!(42, TzError::Unknown(42).code());
+    }
+
+    #[test]
+    fn error_display() {
+        assert_eq!("Empty input string", Error::EmptyInput.to_string());
+        assert_eq!(
+            "Input string contains a NUL byte at position 5",
+            Error::InteriorNul(5).to_string()
+        );
+        assert_eq!(
+            "Invalid timezone: No timezone with this name could be found",
+            Error::Timezone(TzError::NoSuchTimezone).to_string()
+        );
+        assert_eq!(
+            "No timezone database could be loaded from /nope",
+            Error::InvalidDatabase("/nope".into()).to_string()
+        );
+        assert_eq!(
+            "Invalid TZif data for timezone Europe/Nowhere",
+            Error::InvalidTzif("Europe/Nowhere".into()).to_string()
+        );
+        assert_eq!(
+            "Duplicate timezone name: europe/london",
+            Error::DuplicateTimezone("europe/london".into()).to_string()
+        );
+        assert_eq!(
+            "Invalid UTC offset: 05:30",
+            Error::InvalidOffset("05:30".into()).to_string()
+        );
+        assert_eq!(
+            "Invalid ISO 8601 interval: R5",
+            Error::InvalidInterval("R5".into()).to_string()
+        );
+        let parse = Error::Parse(vec![ParseError {
+            message: "Unexpected character".into(),
+            position: 3,
+            character: b'!',
+            code: TIMELIB_ERR_UNEXPECTED_CHARACTER as i32,
+        }]);
+        assert_eq!(
+            "Parse error: Unexpected character at position 3",
+            parse.to_string()
+        );
+    }
+}
@@ -1,3 +1,4 @@
+#![allow(dead_code)]
 #![allow(deref_nullptr)]
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
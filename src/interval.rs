@@ -0,0 +1,499 @@
+use std::fmt;
+
+use crate::{internal::*, parse, Error, IsoInterval, Timezone};
+
+/// One of timelib's functions that apply a relative time to a `timelib_time`.
+pub(crate) type ApplyFn =
+    unsafe extern "C" fn(*mut timelib_time, *mut timelib_rel_time) -> *mut timelib_time;
+
+/// A relative time interval, wrapping timelib's `timelib_rel_time`.
+#[derive(Clone, Copy)]
+pub struct Interval {
+    pub(crate) rt: timelib_rel_time,
+}
+
+impl Interval {
+    /// Returns an interval of the given calendar units, e.g. 1 month and 2 days.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let interval = timelib::Interval::new(0, 1, 2, 0, 0, 0);
+    /// assert_eq!(1, interval.months());
+    /// assert_eq!(2, interval.days());
+    /// ```
+    pub fn new(years: i64, months: i64, days: i64, hours: i64, minutes: i64, seconds: i64) -> Self {
+        // A zeroed timelib_rel_time is an empty interval.
+        let mut rt: timelib_rel_time = unsafe { std::mem::zeroed() };
+        rt.y = years;
+        rt.m = months;
+        rt.d = days;
+        rt.h = hours;
+        rt.i = minutes;
+        rt.s = seconds;
+        rt.days = TIMELIB_UNSET as timelib_sll;
+        Self { rt }
+    }
+
+    /// Parses the relative parts of a date/time string, like PHP's
+    /// `DateInterval::createFromDateString()`, or returns an error. A string without relative
+    /// parts results in an empty interval.
+    ///
+    /// # Arguments
+    ///
+    /// * `relative` - A string that holds the relative time, e.g. "+1 month" or "next tuesday".
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let interval = timelib::Interval::from_date_string("1 year + 2 days").unwrap();
+    /// assert_eq!(1, interval.years());
+    /// assert_eq!(2, interval.days());
+    /// ```
+    pub fn from_date_string(relative: &str) -> Result<Self, Error> {
+        Ok(parse(relative)?
+            .relative()
+            .unwrap_or_else(|| Self::new(0, 0, 0, 0, 0, 0)))
+    }
+
+    /// Parses an ISO 8601 duration such as "P1DT12H", like PHP's `DateInterval` constructor, or
+    /// returns an error. Like PHP, an interval given as a start and end, e.g.
+    /// "2008-03-01T13:00:00Z/2008-05-11T15:30:00Z", results in the difference between them.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - A string that holds the ISO 8601 duration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let interval = timelib::Interval::parse_iso("P1DT12H").unwrap();
+    /// assert_eq!(1, interval.days());
+    /// assert_eq!(12, interval.hours());
+    /// ```
+    pub fn parse_iso(duration: &str) -> Result<Self, Error> {
+        let interval = IsoInterval::parse(duration)?;
+        match interval {
+            IsoInterval {
+                period: Some(period),
+                ..
+            } => Ok(period),
+            IsoInterval {
+                start: Some(start),
+                end: Some(end),
+                ..
+            } => Ok(Self::diff(start, end, &Timezone::from_offset(0))),
+            _ => Err(Error::InvalidInterval(duration.into())),
+        }
+    }
+
+    /// Wraps a copy of the given timelib relative time.
+    pub(crate) fn from_raw(rt: &timelib_rel_time) -> Self {
+        Self { rt: *rt }
+    }
+
+    /// Returns the calendar difference between two timestamps in the given timezone, inverted if
+    /// `two` is before `one`.
+    pub(crate) fn diff(one: i64, two: i64, timezone: &Timezone) -> Self {
+        unsafe {
+            let (one, two) = (timezone.local_time(one), timezone.local_time(two));
+            let rt = timelib_diff(one, two);
+            let interval = Self::from_raw(&*rt);
+            timelib_rel_time_dtor(rt);
+            timelib_time_dtor(one);
+            timelib_time_dtor(two);
+            interval
+        }
+    }
+
+    /// Returns the number of full days between two timestamps in the given timezone.
+    pub(crate) fn diff_days(one: i64, two: i64, timezone: &Timezone) -> i64 {
+        unsafe {
+            let (one, two) = (timezone.local_time(one), timezone.local_time(two));
+            let days = timelib_diff_days(one, two);
+            timelib_time_dtor(one);
+            timelib_time_dtor(two);
+            days.into()
+        }
+    }
+
+    /// Applies the interval to a timestamp in the given timezone with one of timelib's
+    /// add/subtract functions and returns the resulting timestamp.
+    pub(crate) fn apply(&self, timestamp: i64, timezone: &Timezone, apply: ApplyFn) -> i64 {
+        unsafe {
+            let time = timezone.local_time(timestamp);
+            let mut rt = self.rt;
+            let result = apply(time, &mut rt);
+            let seconds = (*result).sse;
+            timelib_time_dtor(result);
+            timelib_time_dtor(time);
+            seconds
+        }
+    }
+
+    /// Returns the number of years.
+    pub fn years(&self) -> i64 {
+        self.rt.y
+    }
+
+    /// Returns the number of months.
+    pub fn months(&self) -> i64 {
+        self.rt.m
+    }
+
+    /// Returns the number of days.
+    // `rt.days` holds the total number of days instead; see `total_days`.
+    #[allow(clippy::misnamed_getters)]
+    pub fn days(&self) -> i64 {
+        self.rt.d
+    }
+
+    /// Returns the number of hours.
+    pub fn hours(&self) -> i64 {
+        self.rt.h
+    }
+
+    /// Returns the number of minutes.
+    pub fn minutes(&self) -> i64 {
+        self.rt.i
+    }
+
+    /// Returns the number of seconds.
+    pub fn seconds(&self) -> i64 {
+        self.rt.s
+    }
+
+    /// Returns the number of microseconds.
+    pub fn microseconds(&self) -> i64 {
+        self.rt.us
+    }
+
+    /// Returns whether the interval is negative.
+    pub fn is_inverted(&self) -> bool {
+        self.rt.invert != 0
+    }
+
+    /// Returns the total number of days the interval spans, if known.
+    pub fn total_days(&self) -> Option<i64> {
+        (self.rt.days != TIMELIB_UNSET as i64).then_some(self.rt.days)
+    }
+
+    /// Returns the relative weekday (0 = Sunday through 6 = Saturday), e.g. for "next tuesday".
+    pub fn weekday(&self) -> Option<i32> {
+        (self.rt.have_weekday_relative != 0).then_some(self.rt.weekday)
+    }
+
+    /// Returns the number of weekdays to move, e.g. for "+3 weekdays".
+    pub fn weekdays(&self) -> Option<i64> {
+        (self.rt.have_special_relative != 0 && self.rt.special.type_ == TIMELIB_SPECIAL_WEEKDAY)
+            .then_some(self.rt.special.amount)
+    }
+
+    /// Returns whether the interval moves to the first day of the month.
+    pub fn is_first_day_of(&self) -> bool {
+        self.rt.first_last_day_of == TIMELIB_SPECIAL_FIRST_DAY_OF_MONTH as i32
+    }
+
+    /// Returns whether the interval moves to the last day of the month.
+    pub fn is_last_day_of(&self) -> bool {
+        self.rt.first_last_day_of == TIMELIB_SPECIAL_LAST_DAY_OF_MONTH as i32
+    }
+
+    /// Formats the interval as an ISO 8601 duration, e.g. "P1Y2M3DT4H", leaving out units that
+    /// are zero.
+    ///
+    /// ISO 8601 durations have a single sign, so inverted intervals and intervals whose units are
+    /// all negative are prefixed with "-" instead. Returns `None` if some units are positive and
+    /// others negative, like in "+1 month -2 hours".
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let interval = timelib::Interval::new(1, 2, 3, 4, 0, 0);
+    /// assert_eq!(Some("P1Y2M3DT4H".into()), interval.to_iso());
+    /// assert_eq!(Some("-P1MT2H".into()), timelib::Interval::new(0, -1, 0, -2, 0, 0).to_iso());
+    /// assert_eq!(None, timelib::Interval::new(0, 1, 0, -2, 0, 0).to_iso());
+    /// ```
+    pub fn to_iso(&self) -> Option<String> {
+        let rt = &self.rt;
+        let units = [rt.y, rt.m, rt.d, rt.h, rt.i, rt.s * 1_000_000 + rt.us];
+        let negative = units.iter().any(|&value| value < 0);
+        if negative && units.iter().any(|&value| value > 0) {
+            return None;
+        }
+        let [y, m, d, h, i, us] = units.map(i64::unsigned_abs);
+
+        let mut iso = String::from(if self.is_inverted() != negative {
+            "-P"
+        } else {
+            "P"
+        });
+        for (value, unit) in [(y, 'Y'), (m, 'M'), (d, 'D')] {
+            if value != 0 {
+                iso += &format!("{value}{unit}");
+            }
+        }
+        if h != 0 || i != 0 || us != 0 {
+            iso.push('T');
+            for (value, unit) in [(h, 'H'), (i, 'M')] {
+                if value != 0 {
+                    iso += &format!("{value}{unit}");
+                }
+            }
+            if us % 1_000_000 != 0 {
+                let seconds = format!("{}.{:06}", us / 1_000_000, us % 1_000_000);
+                iso += seconds.trim_end_matches('0');
+                iso.push('S');
+            } else if us != 0 {
+                iso += &format!("{}S", us / 1_000_000);
+            }
+        } else if y == 0 && m == 0 && d == 0 {
+            iso += "T0S";
+        }
+        Some(iso)
+    }
+
+    /// Formats the interval like PHP's `DateInterval::format()`.
+    ///
+    /// Supported placeholders are `%y`, `%m`, `%d`, `%h`, `%i` and `%s` for the units (`%Y`,
+    /// `%M`, `%D`, `%H`, `%I` and `%S` pad them to two digits), `%f` and `%F` for microseconds,
+    /// `%a` for the total number of days, `%R` for "+" or "-", `%r` for "-" if inverted and `%%`
+    /// for a literal "%". Other characters are copied verbatim.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let interval = timelib::Interval::new(1, 2, 3, 4, 5, 6);
+    /// assert_eq!("+1y 02m 3d 04:05:06", interval.format("%R%yy %Mm %dd %H:%I:%S"));
+    /// ```
+    pub fn format(&self, format: &str) -> String {
+        let rt = &self.rt;
+        let mut out = String::with_capacity(format.len());
+        let mut chars = format.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.push(c);
+                continue;
+            }
+            let Some(spec) = chars.next() else {
+                break;
+            };
+            match spec {
+                'Y' => out += &format!("{:02}", rt.y),
+                'y' => out += &rt.y.to_string(),
+                'M' => out += &format!("{:02}", rt.m),
+                'm' => out += &rt.m.to_string(),
+                'D' => out += &format!("{:02}", rt.d),
+                'd' => out += &rt.d.to_string(),
+                'H' => out += &format!("{:02}", rt.h),
+                'h' => out += &rt.h.to_string(),
+                'I' => out += &format!("{:02}", rt.i),
+                'i' => out += &rt.i.to_string(),
+                'S' => out += &format!("{:02}", rt.s),
+                's' => out += &rt.s.to_string(),
+                'F' => out += &format!("{:06}", rt.us),
+                'f' => out += &rt.us.to_string(),
+                'a' => match self.total_days() {
+                    Some(days) => out += &days.to_string(),
+                    None => out += "(unknown)",
+                },
+                'R' => out.push(if self.is_inverted() { '-' } else { '+' }),
+                'r' if self.is_inverted() => out.push('-'),
+                'r' => {}
+                '%' => out.push('%'),
+                other => {
+                    out.push('%');
+                    out.push(other);
+                }
+            }
+        }
+        out
+    }
+}
+
+impl fmt::Debug for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Interval")
+            .field("years", &self.years())
+            .field("months", &self.months())
+            .field("days", &self.days())
+            .field("hours", &self.hours())
+            .field("minutes", &self.minutes())
+            .field("seconds", &self.seconds())
+            .field("microseconds", &self.microseconds())
+            .field("inverted", &self.is_inverted())
+            .field("total_days", &self.total_days())
+            .field("weekday", &self.weekday())
+            .field("weekdays", &self.weekdays())
+            .field("first_day_of", &self.is_first_day_of())
+            .field("last_day_of", &self.is_last_day_of())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interval_relative_units() {
+        let relative = parse("+1 year 2 months -3 days 4 hours")
+            .unwrap()
+            .relative()
+            .unwrap();
+        assert_eq!(1, relative.years());
+        assert_eq!(2, relative.months());
+        assert_eq!(-3, relative.days());
+        assert_eq!(4, relative.hours());
+        assert_eq!(0, relative.minutes());
+        assert_eq!(None, relative.weekday());
+        assert!(!relative.is_inverted());
+    }
+
+    #[test]
+    fn interval_relative_weekday() {
+        let relative = parse("next tuesday").unwrap().relative().unwrap();
+        assert_eq!(Some(2), relative.weekday());
+        assert_eq!(None, relative.weekdays());
+    }
+
+    #[test]
+    fn interval_relative_weekdays() {
+        let relative = parse("+3 weekdays").unwrap().relative().unwrap();
+        assert_eq!(Some(3), relative.weekdays());
+    }
+
+    #[test]
+    fn interval_relative_first_last_day_of() {
+        let relative = parse("first day of next month")
+            .unwrap()
+            .relative()
+            .unwrap();
+        assert!(relative.is_first_day_of());
+        assert!(!relative.is_last_day_of());
+        assert_eq!(1, relative.months());
+        let relative = parse("last day of this month").unwrap().relative().unwrap();
+        assert!(relative.is_last_day_of());
+    }
+
+    #[test]
+    fn interval_new() {
+        let interval = Interval::new(1, 2, 3, 4, 5, 6);
+        assert_eq!(
+            (1, 2, 3, 4, 5, 6),
+            (
+                interval.years(),
+                interval.months(),
+                interval.days(),
+                interval.hours(),
+                interval.minutes(),
+                interval.seconds()
+            )
+        );
+        assert_eq!(0, interval.microseconds());
+        assert!(!interval.is_inverted());
+        assert_eq!(None, interval.total_days());
+        assert_eq!(None, interval.weekday());
+    }
+
+    #[test]
+    fn interval_parse_iso() {
+        let interval = Interval::parse_iso("P1DT12H").unwrap();
+        assert_eq!((1, 12), (interval.days(), interval.hours()));
+        assert_eq!(14, Interval::parse_iso("P2W").unwrap().days());
+        let interval = Interval::parse_iso("2008-03-01T13:00:00Z/2008-05-11T15:30:00Z").unwrap();
+        assert_eq!(
+            (2, 10, 2, 30),
+            (
+                interval.months(),
+                interval.days(),
+                interval.hours(),
+                interval.minutes()
+            )
+        );
+        assert_eq!(Some(71), interval.total_days());
+        assert!(matches!(
+            Interval::parse_iso("R5"),
+            Err(Error::InvalidInterval(duration)) if duration == "R5"
+        ));
+        assert!(matches!(Interval::parse_iso("1 day"), Err(Error::Parse(_))));
+    }
+
+    #[test]
+    fn interval_to_iso() {
+        let tz = Timezone::parse("UTC").unwrap();
+        // 2022-01-15 00:00:00 to 2023-03-20 10:30:15
+        let interval = crate::diff(1642204800, 1679308215, &tz);
+        assert_eq!(Some("P1Y2M5DT10H30M15S".into()), interval.to_iso());
+        assert_eq!(
+            Some("-P1Y2M5DT10H30M15S".into()),
+            crate::diff(1679308215, 1642204800, &tz).to_iso()
+        );
+        let iso = |interval: Interval| interval.to_iso().unwrap();
+        assert_eq!("PT0S", iso(Interval::new(0, 0, 0, 0, 0, 0)));
+        assert_eq!("P14D", iso(Interval::parse_iso("P2W").unwrap()));
+        assert_eq!("PT1M", iso(Interval::new(0, 0, 0, 0, 1, 0)));
+        let precise = Interval::from_date_string("+1 sec +500000 usec").unwrap();
+        assert_eq!("PT1.5S", iso(precise));
+        assert_eq!("P1DT12H", iso(Interval::parse_iso("P1DT12H").unwrap()));
+    }
+
+    #[test]
+    fn interval_to_iso_negative() {
+        let tz = Timezone::parse("UTC").unwrap();
+        let start = 1704067200; // 2024-01-01
+        let negative = Interval::from_date_string("-1 month -2 hours").unwrap();
+        let iso = negative.to_iso().unwrap();
+        assert_eq!("-P1MT2H", iso);
+        // timelib's ISO 8601 parser has no sign, so subtract the unsigned duration instead.
+        let unsigned = Interval::parse_iso(iso.strip_prefix('-').unwrap()).unwrap();
+        assert_eq!(
+            crate::add(start, &negative, &tz),
+            crate::sub(start, &unsigned, &tz)
+        );
+        let precise = Interval::from_date_string("-1 sec -500000 usec").unwrap();
+        assert_eq!(Some("-PT1.5S".into()), precise.to_iso());
+        // Negative units of an inverted interval cancel out.
+        let mut inverted = Interval::new(0, 0, -1, 0, 0, 0);
+        inverted.rt.invert = 1;
+        assert_eq!(Some("P1D".into()), inverted.to_iso());
+        assert_eq!(
+            None,
+            Interval::from_date_string("+1 month -2 hours")
+                .unwrap()
+                .to_iso()
+        );
+    }
+
+    #[test]
+    fn interval_format() {
+        let tz = Timezone::parse("UTC").unwrap();
+        let interval = crate::diff(1642204800, 1679308215, &tz);
+        assert_eq!(
+            "+1 years 2 months 5 days, 429 total",
+            interval.format("%R%y years %m months %d days, %a total")
+        );
+        assert_eq!(
+            "01-02-05 10:30:15.000000 0 %",
+            interval.format("%Y-%M-%D %H:%I:%S.%F %f %%")
+        );
+        let inverted = crate::diff(1679308215, 1642204800, &tz);
+        assert_eq!("-1 -", inverted.format("%r%y %R"));
+        assert_eq!("1 +", interval.format("%r%y %R"));
+        let unknown = Interval::new(0, 0, 0, 0, 0, 0);
+        assert_eq!("(unknown) %x", unknown.format("%a %x%"));
+    }
+
+    #[test]
+    fn interval_from_date_string() {
+        let interval = Interval::from_date_string("+1 month -2 hours").unwrap();
+        assert_eq!(1, interval.months());
+        assert_eq!(-2, interval.hours());
+        let interval = Interval::from_date_string("2024-01-01").unwrap();
+        assert_eq!(0, interval.days());
+        assert!(matches!(
+            Interval::from_date_string(""),
+            Err(Error::EmptyInput)
+        ));
+    }
+}
@@ -0,0 +1,128 @@
+use std::{ffi::CString, ptr};
+
+use crate::{internal::*, Error, Interval, ParseError};
+
+/// An ISO 8601 time interval such as "R5/2008-03-01T13:00:00Z/P1Y2M10DT2H30M", parsed by
+/// [`parse_iso_interval`](crate::parse_iso_interval).
+///
+/// Only the parts present in the string are set.
+#[derive(Debug, Clone, Copy)]
+pub struct IsoInterval {
+    /// The start, in seconds since the epoch.
+    pub start: Option<i64>,
+    /// The end, in seconds since the epoch.
+    pub end: Option<i64>,
+    /// The duration between occurrences, e.g. "P1Y2M10DT2H30M".
+    pub period: Option<Interval>,
+    /// The number of recurrences after the start, e.g. 5 for "R5".
+    pub recurrences: Option<u32>,
+}
+
+impl IsoInterval {
+    /// Parses an ISO 8601 interval with timelib's `timelib_strtointerval`.
+    pub(crate) fn parse(interval: &str) -> Result<Self, Error> {
+        if interval.is_empty() {
+            return Err(Error::EmptyInput);
+        }
+
+        let interval_c_str =
+            CString::new(interval).map_err(|e| Error::InteriorNul(e.nul_position()))?;
+
+        unsafe {
+            let mut begin = ptr::null_mut();
+            let mut end = ptr::null_mut();
+            let mut period = ptr::null_mut();
+            // Only written if the string holds a recurrence count.
+            let mut recurrences = -1;
+            let mut error = ptr::null_mut();
+            timelib_strtointerval(
+                interval_c_str.as_ptr(),
+                interval_c_str.to_bytes().len(),
+                &mut begin,
+                &mut end,
+                &mut period,
+                &mut recurrences,
+                &mut error,
+            );
+            let errors = ParseError::from_messages((*error).error_messages, (*error).error_count);
+            timelib_error_container_dtor(error);
+
+            let result = IsoInterval {
+                start: timestamp(begin),
+                end: timestamp(end),
+                period: (!period.is_null()).then(|| Interval::from_raw(&*period)),
+                recurrences: u32::try_from(recurrences).ok(),
+            };
+            for time in [begin, end] {
+                if !time.is_null() {
+                    timelib_time_dtor(time);
+                }
+            }
+            if !period.is_null() {
+                timelib_rel_time_dtor(period);
+            }
+
+            if !errors.is_empty() {
+                return Err(Error::Parse(errors));
+            }
+            Ok(result)
+        }
+    }
+}
+
+/// Computes the timestamp of a start or end time parsed by `timelib_strtointerval`, which is in
+/// UTC unless the string gave an offset.
+unsafe fn timestamp(time: *mut timelib_time) -> Option<i64> {
+    if time.is_null() {
+        return None;
+    }
+    timelib_update_ts(time, ptr::null_mut());
+    Some((*time).sse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iso_interval_recurrences() {
+        let interval = IsoInterval::parse("R5/2008-03-01T13:00:00Z/P1Y2M10DT2H30M").unwrap();
+        assert_eq!(Some(1204376400), interval.start);
+        assert_eq!(None, interval.end);
+        assert_eq!(Some(5), interval.recurrences);
+        let period = interval.period.unwrap();
+        assert_eq!(
+            (1, 2, 10, 2, 30, 0),
+            (
+                period.years(),
+                period.months(),
+                period.days(),
+                period.hours(),
+                period.minutes(),
+                period.seconds()
+            )
+        );
+    }
+
+    #[test]
+    fn iso_interval_start_end() {
+        let interval = IsoInterval::parse("2008-03-01T13:00:00Z/2008-05-11T15:30:00Z").unwrap();
+        assert_eq!(Some(1204376400), interval.start);
+        assert_eq!(Some(1210519800), interval.end);
+        assert!(interval.period.is_none());
+        assert_eq!(None, interval.recurrences);
+    }
+
+    #[test]
+    fn iso_interval_errors() {
+        assert!(matches!(IsoInterval::parse(""), Err(Error::EmptyInput)));
+        assert!(matches!(
+            IsoInterval::parse("P1D\0"),
+            Err(Error::InteriorNul(3))
+        ));
+        assert!(matches!(
+            IsoInterval::parse("every day"),
+            Err(Error::Parse(errors)) if !errors.is_empty()
+        ));
+    }
+}
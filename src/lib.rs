@@ -1,13 +1,40 @@
+mod abbreviation;
+mod cache;
+mod date_format;
+mod error;
 mod internal;
+mod interval;
+mod iso_interval;
+mod local;
+mod parsed_time;
+mod period;
+mod timestamp;
+mod timezone;
+mod transitions;
+mod tzdb;
+mod tzif;
+mod zone_offset;
 
 use std::{
-    ffi::{CStr, CString},
+    ffi::CString,
     time::{SystemTime, UNIX_EPOCH},
 };
 
+pub use abbreviation::Abbreviation;
+pub use cache::{cache_capacity, cache_stats, clear_cache, set_cache_capacity, CacheStats};
+pub use error::{Error, ParseError, ParseWarning, TzError};
 use internal::*;
+pub use interval::Interval;
+pub use iso_interval::IsoInterval;
+pub use parsed_time::ParsedTime;
+pub use period::Period;
+pub use timestamp::Timestamp;
+pub use timezone::Timezone;
+pub use transitions::{Transition, Transitions};
+pub use tzdb::TzDb;
+pub use zone_offset::ZoneOffset;
 
-/// Returns a timestamp (in seconds since the epoch) or an error (string).
+/// Returns a timestamp (in seconds since the epoch) or an error.
 ///
 /// # Arguments
 ///
@@ -26,103 +53,447 @@ pub fn strtotime(
     date_time: &str,
     base_timestamp: Option<i64>,
     timezone: &Timezone,
-) -> Result<i64, String> {
-    if date_time.is_empty() {
-        return Err("Empty date_time string.".into());
-    }
+) -> Result<i64, Error> {
+    strtotime_with_warnings(date_time, base_timestamp, timezone).map(|(timestamp, _)| timestamp)
+}
 
-    let Ok(date_time_c_str) = CString::new(date_time) else {
-        return Err("Malformed date_time string.".into());
-    };
+/// Returns a timestamp (in seconds since the epoch) or an error, looking up any timezone named
+/// in `date_time` in the given timezone database.
+///
+/// # Arguments
+///
+/// * `date_time` - A string that holds the relative date you wish to compute.
+/// * `base_timestamp` - An optional timestamp (in seconds) to use as your base (defaults to the current timestamp).
+/// * `timezone` - An address of a Timezone object.
+/// * `db` - The timezone database to look up timezones named in `date_time` in.
+///
+/// # Examples
+///
+/// ```
+/// let db = timelib::TzDb::builtin();
+/// let tz = timelib::Timezone::parse_with_db("UTC", &db).expect("Error parsing timezone!");
+/// timelib::strtotime_with_db("2006-05-12 13:00:00 America/New_York", None, &tz, &db);
+/// ```
+pub fn strtotime_with_db(
+    date_time: &str,
+    base_timestamp: Option<i64>,
+    timezone: &Timezone,
+    db: &TzDb,
+) -> Result<i64, Error> {
+    Ok(parse_with_db(date_time, db)?.to_timestamp(base_timestamp, timezone))
+}
 
-    unsafe {
-        let mut error = std::mem::MaybeUninit::uninit();
-        let parsed_time = timelib_strtotime(
-            date_time_c_str.as_ptr(),
-            date_time_c_str.to_bytes().len(),
-            error.as_mut_ptr(),
-            timelib_builtin_db(),
-            Some(timelib_tz_get_wrapper_cached),
-        );
-        let err_count = (*error.assume_init()).error_count;
-        timelib_error_container_dtor(error.assume_init());
-        if err_count != 0 {
-            timelib_time_dtor(parsed_time);
-            // TODO expose error message(s)
-            return Err("Invalid date_time string.".into());
-        }
+/// Returns a timestamp (in seconds since the epoch) along with any warnings timelib reported
+/// while parsing, or an error.
+///
+/// # Arguments
+///
+/// * `date_time` - A string that holds the relative date you wish to compute.
+/// * `base_timestamp` - An optional timestamp (in seconds) to use as your base (defaults to the current timestamp).
+/// * `timezone` - An address of a Timezone object.
+///
+/// # Examples
+///
+/// ```
+/// let tz = timelib::Timezone::parse("UTC").expect("Error parsing timezone!");
+/// let (timestamp, warnings) = timelib::strtotime_with_warnings("2023-02-30", None, &tz).unwrap();
+/// assert_eq!(1677715200, timestamp); // 2023-03-02
+/// assert_eq!("The parsed date was invalid", warnings[0].message);
+/// ```
+pub fn strtotime_with_warnings(
+    date_time: &str,
+    base_timestamp: Option<i64>,
+    timezone: &Timezone,
+) -> Result<(i64, Vec<ParseWarning>), Error> {
+    let parsed = parse(date_time)?;
+    let timestamp = parsed.to_timestamp(base_timestamp, timezone);
+    Ok((timestamp, parsed.warnings().to_vec()))
+}
 
-        let base = timelib_time_ctor();
-        (*base).tz_info = timezone.tzi;
-        (*base).zone_type = TIMELIB_ZONETYPE_ID;
-        timelib_unixtime2local(base, base_timestamp.unwrap_or_else(rust_now_sec));
+/// Returns a timestamp with microsecond precision or an error.
+///
+/// # Arguments
+///
+/// * `date_time` - A string that holds the relative date you wish to compute.
+/// * `base_timestamp` - An optional timestamp (in seconds) to use as your base (defaults to the current timestamp).
+/// * `timezone` - An address of a Timezone object.
+///
+/// # Examples
+///
+/// ```
+/// let tz = timelib::Timezone::parse("UTC").expect("Error parsing timezone!");
+/// let timestamp = timelib::strtotime_precise("2024-01-01 12:00:00.123456", None, &tz).unwrap();
+/// assert_eq!(1704110400, timestamp.seconds);
+/// assert_eq!(123456, timestamp.microseconds);
+/// let system_time: std::time::SystemTime = timestamp.into();
+/// ```
+pub fn strtotime_precise(
+    date_time: &str,
+    base_timestamp: Option<i64>,
+    timezone: &Timezone,
+) -> Result<Timestamp, Error> {
+    Ok(parse(date_time)?.to_timestamp_precise(base_timestamp, timezone))
+}
 
-        timelib_fill_holes(parsed_time, base, TIMELIB_NO_CLONE as i32);
-        timelib_update_ts(parsed_time, timezone.tzi);
-        let result = (*parsed_time).sse;
-        timelib_time_dtor(parsed_time);
-        timelib_time_dtor(base);
+/// Formats a timestamp (in seconds since the epoch) in the given timezone, like PHP's `date()`.
+///
+/// Every PHP format character is supported, e.g. "Y-m-d" or "D, d M Y H:i:s O"; a backslash
+/// escapes the next character and unknown characters are copied verbatim. For fixed offsets,
+/// "e" and "T" render the offset, e.g. "+05:30".
+///
+/// # Arguments
+///
+/// * `format` - A PHP `date()` format string.
+/// * `timestamp` - The timestamp (in seconds) to format.
+/// * `timezone` - An address of a Timezone object.
+///
+/// # Examples
+///
+/// ```
+/// let tz = timelib::Timezone::parse("America/Chicago").expect("Error parsing timezone!");
+/// assert_eq!(
+///     "Sat, 04 Jun 2022 00:00:23 -0500",
+///     timelib::format("D, d M Y H:i:s O", 1654318823, &tz)
+/// );
+/// assert_eq!("2022-06-04T00:00:23-05:00", timelib::format("c", 1654318823, &tz));
+/// ```
+pub fn format(format: &str, timestamp: i64, timezone: &Timezone) -> String {
+    format_precise(
+        format,
+        Timestamp {
+            seconds: timestamp,
+            microseconds: 0,
+        },
+        timezone,
+    )
+}
 
-        Ok(result)
-    }
+/// Formats a timestamp with microsecond precision in the given timezone, like PHP's
+/// `DateTime::format()`. See [`format`] for the supported format characters.
+///
+/// # Arguments
+///
+/// * `format` - A PHP `date()` format string.
+/// * `timestamp` - The timestamp to format.
+/// * `timezone` - An address of a Timezone object.
+///
+/// # Examples
+///
+/// ```
+/// let tz = timelib::Timezone::parse("UTC").expect("Error parsing timezone!");
+/// let timestamp = timelib::strtotime_precise("2024-01-01 12:00:00.123456", None, &tz).unwrap();
+/// assert_eq!("12:00:00.123456", timelib::format_precise("H:i:s.u", timestamp, &tz));
+/// ```
+pub fn format_precise(format: &str, timestamp: Timestamp, timezone: &Timezone) -> String {
+    date_format::format(format, timestamp, timezone)
 }
 
-fn rust_now_sec() -> i64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs() as i64
+/// Adds an interval to a timestamp (in seconds since the epoch) in the given timezone, like
+/// PHP's `DateTime::modify()`.
+///
+/// All units, including hours, minutes and seconds, move the local wall clock: adding 1 day
+/// keeps the time of day across DST changes, and adding 1 month to January 31st overflows into
+/// March. See [`add_wall`] to add the time units as elapsed time instead.
+///
+/// # Arguments
+///
+/// * `timestamp` - The timestamp (in seconds) to add to.
+/// * `interval` - The interval to add; relative weekdays such as "next tuesday" are supported.
+/// * `timezone` - An address of a Timezone object.
+///
+/// # Examples
+///
+/// ```
+/// let tz = timelib::Timezone::parse("UTC").expect("Error parsing timezone!");
+/// let month = timelib::Interval::new(0, 1, 0, 0, 0, 0);
+/// // 2023-01-31 + 1 month = 2023-03-03
+/// assert_eq!(1677801600, timelib::add(1675123200, &month, &tz));
+/// ```
+pub fn add(timestamp: i64, interval: &Interval, timezone: &Timezone) -> i64 {
+    interval.apply(timestamp, timezone, timelib_add)
 }
 
-/// A Timezone wrapper.
-#[derive(Debug, PartialEq)]
-pub struct Timezone {
-    tzi: *mut timelib_tzinfo,
+/// Adds an interval to a timestamp (in seconds since the epoch) in the given timezone, like
+/// PHP's `DateTime::add()`.
+///
+/// Years, months and days move the local calendar date, while hours, minutes and seconds are
+/// added as elapsed time, so adding 1 hour always moves the timestamp by 3600 seconds.
+///
+/// # Arguments
+///
+/// * `timestamp` - The timestamp (in seconds) to add to.
+/// * `interval` - The interval to add.
+/// * `timezone` - An address of a Timezone object.
+///
+/// # Examples
+///
+/// ```
+/// let tz = timelib::Timezone::parse("America/Chicago").expect("Error parsing timezone!");
+/// let hour = timelib::Interval::new(0, 0, 0, 1, 0, 0);
+/// assert_eq!(1730615400, timelib::add_wall(1730611800, &hour, &tz));
+/// ```
+pub fn add_wall(timestamp: i64, interval: &Interval, timezone: &Timezone) -> i64 {
+    interval.apply(timestamp, timezone, timelib_add_wall)
 }
 
-impl Drop for Timezone {
-    fn drop(&mut self) {
-        unsafe {
-            timelib_tzinfo_dtor(self.tzi);
-        }
+/// Subtracts an interval from a timestamp (in seconds since the epoch) in the given timezone.
+///
+/// The counterpart of [`add`]; relative weekdays and other special relative times are not
+/// supported.
+///
+/// # Arguments
+///
+/// * `timestamp` - The timestamp (in seconds) to subtract from.
+/// * `interval` - The interval to subtract.
+/// * `timezone` - An address of a Timezone object.
+///
+/// # Examples
+///
+/// ```
+/// let tz = timelib::Timezone::parse("UTC").expect("Error parsing timezone!");
+/// let month = timelib::Interval::new(0, 1, 0, 0, 0, 0);
+/// // 2023-03-31 - 1 month = 2023-03-03
+/// assert_eq!(1677801600, timelib::sub(1680220800, &month, &tz));
+/// ```
+pub fn sub(timestamp: i64, interval: &Interval, timezone: &Timezone) -> i64 {
+    interval.apply(timestamp, timezone, timelib_sub)
+}
+
+/// Subtracts an interval from a timestamp (in seconds since the epoch) in the given timezone,
+/// like PHP's `DateTime::sub()`.
+///
+/// The counterpart of [`add_wall`]: hours, minutes and seconds are subtracted as elapsed time.
+///
+/// # Arguments
+///
+/// * `timestamp` - The timestamp (in seconds) to subtract from.
+/// * `interval` - The interval to subtract.
+/// * `timezone` - An address of a Timezone object.
+///
+/// # Examples
+///
+/// ```
+/// let tz = timelib::Timezone::parse("America/Chicago").expect("Error parsing timezone!");
+/// let hour = timelib::Interval::new(0, 0, 0, 1, 0, 0);
+/// assert_eq!(1730611800, timelib::sub_wall(1730615400, &hour, &tz));
+/// ```
+pub fn sub_wall(timestamp: i64, interval: &Interval, timezone: &Timezone) -> i64 {
+    interval.apply(timestamp, timezone, timelib_sub_wall)
+}
+
+/// Returns the calendar difference between two timestamps (in seconds since the epoch) in the
+/// given timezone, like PHP's `DateTime::diff()`.
+///
+/// The interval holds the years, months, days, hours, minutes and seconds from `one` to `two`,
+/// counted on the local calendar, along with the total number of days. It is inverted if `two`
+/// is before `one`.
+///
+/// # Arguments
+///
+/// * `one` - The timestamp (in seconds) to count from.
+/// * `two` - The timestamp (in seconds) to count to.
+/// * `timezone` - An address of a Timezone object.
+///
+/// # Examples
+///
+/// ```
+/// let tz = timelib::Timezone::parse("America/Chicago").expect("Error parsing timezone!");
+/// // 2023-01-15 to 2023-07-15, across the start of DST.
+/// let interval = timelib::diff(1673762400, 1689397200, &tz);
+/// assert_eq!(6, interval.months());
+/// assert_eq!(0, interval.hours());
+/// assert_eq!(Some(181), interval.total_days());
+/// ```
+pub fn diff(one: i64, two: i64, timezone: &Timezone) -> Interval {
+    Interval::diff(one, two, timezone)
+}
+
+/// Returns the number of full days between two timestamps (in seconds since the epoch) in the
+/// given timezone, counted on the local calendar so that DST changes don't shorten a day.
+///
+/// # Arguments
+///
+/// * `one` - The timestamp (in seconds) to count from.
+/// * `two` - The timestamp (in seconds) to count to.
+/// * `timezone` - An address of a Timezone object.
+///
+/// # Examples
+///
+/// ```
+/// let tz = timelib::Timezone::parse("America/Chicago").expect("Error parsing timezone!");
+/// // 2024-03-09 12:00 CST to 2024-03-10 12:00 CDT is only 23 hours.
+/// assert_eq!(1, timelib::diff_days(1710007200, 1710090000, &tz));
+/// ```
+pub fn diff_days(one: i64, two: i64, timezone: &Timezone) -> i64 {
+    Interval::diff_days(one, two, timezone)
+}
+
+/// Parses an ISO 8601 time interval into its start, end, period and number of recurrences, or
+/// returns an error.
+///
+/// Intervals can be given as "start/end", "start/period", "period/end" or just "period", and
+/// optionally prefixed with a recurrence count, e.g. "R5/2008-03-01T13:00:00Z/P1Y2M10DT2H30M".
+///
+/// # Arguments
+///
+/// * `interval` - A string that holds the ISO 8601 interval.
+///
+/// # Examples
+///
+/// ```
+/// let interval = timelib::parse_iso_interval("R5/2008-03-01T13:00:00Z/P1Y2M10DT2H30M")
+///     .expect("Error parsing interval!");
+/// assert_eq!(Some(1204376400), interval.start);
+/// assert_eq!(Some(5), interval.recurrences);
+/// assert_eq!(10, interval.period.unwrap().days());
+/// ```
+pub fn parse_iso_interval(interval: &str) -> Result<IsoInterval, Error> {
+    IsoInterval::parse(interval)
+}
+
+/// Parses a date/time string without resolving it against a base time or timezone, or returns
+/// an error.
+///
+/// # Arguments
+///
+/// * `date_time` - A string that holds the date you wish to parse.
+///
+/// # Examples
+///
+/// ```
+/// let parsed = timelib::parse("next tuesday").expect("Error parsing date!");
+/// assert!(parsed.has_relative());
+/// assert!(!parsed.has_date());
+///
+/// let parsed = timelib::parse("2024-01-02").expect("Error parsing date!");
+/// assert_eq!(Some(2024), parsed.year());
+/// assert_eq!(None, parsed.hour());
+/// ```
+pub fn parse(date_time: &str) -> Result<ParsedTime, Error> {
+    parse_with_db(date_time, &TzDb::builtin())
+}
+
+/// Parses a date/time string without resolving it against a base time or timezone, looking up
+/// any timezone named in it in the given timezone database, or returns an error.
+///
+/// # Arguments
+///
+/// * `date_time` - A string that holds the date you wish to parse.
+/// * `db` - The timezone database to look up timezones named in `date_time` in.
+///
+/// # Examples
+///
+/// ```
+/// let db = timelib::TzDb::builtin();
+/// let parsed = timelib::parse_with_db("2006-05-12 13:00:00 America/New_York", &db)
+///     .expect("Error parsing date!");
+/// assert_eq!(Some("America/New_York".to_string()), parsed.timezone_id());
+/// ```
+pub fn parse_with_db(date_time: &str, db: &TzDb) -> Result<ParsedTime, Error> {
+    if date_time.is_empty() {
+        return Err(Error::EmptyInput);
     }
+
+    let date_time_c_str =
+        CString::new(date_time).map_err(|e| Error::InteriorNul(e.nul_position()))?;
+
+    run_parser(|error| unsafe {
+        timelib_strtotime(
+            date_time_c_str.as_ptr(),
+            date_time_c_str.to_bytes().len(),
+            error,
+            db.as_ptr(),
+            Some(cache::tz_get_wrapper_cached),
+        )
+    })
 }
 
-impl Timezone {
-    /// Parses a String into a Timezone instance.
-    ///
-    /// # Arguments
-    ///
-    /// * `timezone` - A String with your IANA Timezone name.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// let tz = timelib::Timezone::parse("UTC");
-    /// let tz = timelib::Timezone::parse("America/Chicago");
-    /// ```
-    pub fn parse(timezone: &str) -> Result<Timezone, String> {
-        let Ok(tz_c_str) = CString::new(timezone) else {
-            return Err("Malformed timezone string.".into());
-        };
-        let mut error_code: i32 = 0;
-        let error_code_ptr = &mut error_code as *mut i32;
-        unsafe {
-            let tzi = timelib_parse_tzfile(tz_c_str.as_ptr(), timelib_builtin_db(), error_code_ptr);
-            if tzi.is_null() {
-                return Err(format!("Invalid timezone. Err: {error_code}."));
-            }
-            Ok(Self { tzi })
+/// Returns a timestamp parsed according to an explicit format, like PHP's
+/// `DateTime::createFromFormat()`, along with any warnings timelib reported, or an error.
+///
+/// The format uses PHP's format characters, e.g. "d/m/Y H:i". Fields missing from the format
+/// are taken from the base timestamp, including the time of day; start the format with "!" or
+/// end it with "|" to reset them to the Unix epoch instead. "+" ignores trailing data with a
+/// warning, "?" skips a byte, "*" skips up to the next separator or digit and "#" matches any
+/// separator. Timezones parsed with "e", "T", "O" or "P" take precedence over `timezone`.
+///
+/// # Arguments
+///
+/// * `format` - A PHP `DateTime::createFromFormat()` format string.
+/// * `date_time` - A string that holds the date you wish to parse.
+/// * `base_timestamp` - An optional timestamp (in seconds) to use as your base (defaults to the current timestamp).
+/// * `timezone` - An address of a Timezone object.
+///
+/// # Examples
+///
+/// ```
+/// let tz = timelib::Timezone::parse("UTC").expect("Error parsing timezone!");
+/// let (timestamp, warnings) =
+///     timelib::parse_from_format("d/m/Y H:i", "15/08/2023 14:30", None, &tz).unwrap();
+/// assert_eq!(1692109800, timestamp.seconds);
+/// assert!(warnings.is_empty());
+///
+/// let (timestamp, _) = timelib::parse_from_format("!Y-m-d", "2023-08-15", None, &tz).unwrap();
+/// assert_eq!(1692057600, timestamp.seconds);
+/// ```
+pub fn parse_from_format(
+    format: &str,
+    date_time: &str,
+    base_timestamp: Option<i64>,
+    timezone: &Timezone,
+) -> Result<(Timestamp, Vec<ParseWarning>), Error> {
+    let format_c_str = CString::new(format).map_err(|e| Error::InteriorNul(e.nul_position()))?;
+    let date_time_c_str =
+        CString::new(date_time).map_err(|e| Error::InteriorNul(e.nul_position()))?;
+    let db = TzDb::builtin();
+
+    let parsed = run_parser(|error| unsafe {
+        timelib_parse_from_format(
+            format_c_str.as_ptr(),
+            date_time_c_str.as_ptr(),
+            date_time_c_str.to_bytes().len(),
+            error,
+            db.as_ptr(),
+            Some(cache::tz_get_wrapper_cached),
+        )
+    })?;
+    // Like PHP, fields missing from the format come from the base time, even the time of day.
+    let timestamp = parsed.resolve(
+        base_timestamp,
+        timezone,
+        TIMELIB_NO_CLONE | TIMELIB_OVERRIDE_TIME,
+    );
+    Ok((timestamp, parsed.warnings().to_vec()))
+}
+
+/// Runs one of timelib's parsers, pinning the timezones it loads, and collects its errors and
+/// warnings.
+fn run_parser(
+    parse: impl FnOnce(*mut *mut timelib_error_container) -> *mut timelib_time,
+) -> Result<ParsedTime, Error> {
+    unsafe {
+        let mut error = std::mem::MaybeUninit::uninit();
+        let (parsed_time, timezones) = cache::pinning(|| parse(error.as_mut_ptr()));
+        let error = error.assume_init();
+        let errors = ParseError::from_messages((*error).error_messages, (*error).error_count);
+        let warnings = ParseError::from_messages((*error).warning_messages, (*error).warning_count);
+        timelib_error_container_dtor(error);
+        if !errors.is_empty() {
+            timelib_time_dtor(parsed_time);
+            return Err(Error::Parse(errors));
         }
-    }
 
-    /// Returns the underlying timezone database version.
-    pub fn db_version() -> String {
-        let cstr = unsafe { CStr::from_ptr((*timelib_builtin_db()).version) };
-        String::from_utf8_lossy(cstr.to_bytes()).to_string()
+        Ok(ParsedTime::new(parsed_time, warnings, timezones))
     }
 }
 
+fn rust_now_sec() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -131,21 +502,86 @@ mod tests {
     fn strtotime_empty_input() {
         let tz = Timezone::parse("UTC").unwrap();
         let result = strtotime("", None, &tz);
-        assert_eq!(Err("Empty date_time string.".to_string()), result);
+        assert_eq!(Err(Error::EmptyInput), result);
     }
 
     #[test]
     fn strtotime_invalid_date_time() {
         let tz = Timezone::parse("UTC").unwrap();
         let result = strtotime("derp", None, &tz);
-        assert_eq!(Err("Invalid date_time string.".to_string()), result);
+        assert_eq!(
+            Err(Error::Parse(vec![ParseError {
+                message: "The timezone could not be found in the database".into(),
+                position: 0,
+                character: b'd',
+                code: TIMELIB_ERR_TZID_NOT_FOUND as i32,
+            }])),
+            result
+        );
     }
 
     #[test]
     fn strtotime_invalid_date_time_string() {
         let tz = Timezone::parse("UTC").unwrap();
         let result = strtotime("today\0", None, &tz);
-        assert_eq!(Err("Malformed date_time string.".to_string()), result);
+        assert_eq!(Err(Error::InteriorNul(5)), result);
+    }
+
+    #[test]
+    fn parse_error_display() {
+        let tz = Timezone::parse("UTC").unwrap();
+        let Err(Error::Parse(errors)) = strtotime("derp", None, &tz) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(
+            "The timezone could not be found in the database at position 0",
+            errors[0].to_string()
+        );
+    }
+
+    #[test]
+    fn strtotime_with_warnings_invalid_date() {
+        let tz = Timezone::parse("UTC").unwrap();
+        let (result, warnings) = strtotime_with_warnings("2023-02-30", None, &tz).unwrap();
+        assert_eq!(1677715200, result);
+        assert_eq!(1, warnings.len());
+        assert_eq!("The parsed date was invalid", warnings[0].message);
+        assert_eq!(TIMELIB_WARN_INVALID_DATE as i32, warnings[0].code);
+        // The plain variant still succeeds.
+        assert_eq!(Ok(1677715200), strtotime("2023-02-30", None, &tz));
+    }
+
+    #[test]
+    fn strtotime_with_warnings_none() {
+        let tz = Timezone::parse("UTC").unwrap();
+        let result = strtotime_with_warnings("jun 4 2022", None, &tz);
+        assert_eq!(Ok((1654300800, vec![])), result);
+    }
+
+    #[test]
+    fn strtotime_precise_microseconds() {
+        let tz = Timezone::parse("UTC").unwrap();
+        let result = strtotime_precise("2024-01-01 12:00:00.123456", None, &tz);
+        assert_eq!(
+            Ok(Timestamp {
+                seconds: 1704110400,
+                microseconds: 123456,
+            }),
+            result
+        );
+    }
+
+    #[test]
+    fn strtotime_precise_relative_microseconds() {
+        let tz = Timezone::parse("UTC").unwrap();
+        let result = strtotime_precise("2024-01-01 12:00:00.750000 +500000 usec", None, &tz);
+        assert_eq!(
+            Ok(Timestamp {
+                seconds: 1704110401,
+                microseconds: 250000,
+            }),
+            result
+        );
     }
 
     #[test]
@@ -172,8 +608,164 @@ mod tests {
         assert_eq!(Ok(1654318800), result);
     }
 
+    #[test]
+    fn parse_from_format_fields() {
+        let tz = Timezone::parse("UTC").unwrap();
+        let parse = |format, date_time| {
+            parse_from_format(format, date_time, Some(1654318823), &tz).map(|(ts, _)| ts.seconds)
+        };
+        assert_eq!(Ok(1692109800), parse("d/m/Y H:i", "15/08/2023 14:30"));
+        // Missing fields, even the time of day, come from the base timestamp.
+        assert_eq!(Ok(1692075623), parse("Y-m-d", "2023-08-15"));
+        assert_eq!(Ok(1692057600), parse("!Y-m-d", "2023-08-15"));
+        assert_eq!(Ok(1692057600), parse("Y-m-d|", "2023-08-15"));
+        assert_eq!(Ok(1692057600), parse("!Y#m#d", "2023/08.15"));
+        assert_eq!(Ok(1692057600), parse("!Y-m-d *", "2023-08-15 skipped"));
+        assert_eq!(Ok(1692057600), parse("!Y-m-d??", "2023-08-15T1"));
+        assert_eq!(Ok(1654318823), parse("U", "1654318823"));
+    }
+
+    #[test]
+    fn parse_from_format_timezones() {
+        let tz = Timezone::parse("America/Chicago").unwrap();
+        let parse =
+            |format, date_time| parse_from_format(format, date_time, None, &tz).map(|(ts, _)| ts);
+        assert_eq!(
+            Ok(1692075600),
+            parse("!Y-m-d", "2023-08-15").map(|ts| ts.seconds)
+        );
+        assert_eq!(
+            Ok(1147453200),
+            parse("Y-m-d H:i:s e", "2006-05-12 13:00:00 America/New_York").map(|ts| ts.seconds)
+        );
+        assert_eq!(
+            Ok(1692102600),
+            parse("Y-m-d H:i:s P", "2023-08-15 14:30:00 +02:00").map(|ts| ts.seconds)
+        );
+        assert_eq!(
+            Ok(1692102600),
+            parse("Y-m-d H:i:s T", "2023-08-15 14:30:00 CEST").map(|ts| ts.seconds)
+        );
+        assert_eq!(
+            Ok(Timestamp {
+                seconds: 1704088800,
+                microseconds: 123456,
+            }),
+            parse("Y-m-d H:i:s.u O", "2024-01-01 00:00:00.123456 -0600")
+        );
+    }
+
+    #[test]
+    fn parse_from_format_warnings_and_errors() {
+        let tz = Timezone::parse("UTC").unwrap();
+        let (timestamp, warnings) =
+            parse_from_format("!Y-m-d+", "2023-08-15 trailing", None, &tz).unwrap();
+        assert_eq!(1692057600, timestamp.seconds);
+        assert_eq!(TIMELIB_WARN_TRAILING_DATA as i32, warnings[0].code);
+
+        let (timestamp, warnings) = parse_from_format("!Y-m-d", "2023-02-30", None, &tz).unwrap();
+        assert_eq!(1677715200, timestamp.seconds);
+        assert_eq!("The parsed date was invalid", warnings[0].message);
+
+        let Err(Error::Parse(errors)) = parse_from_format("!Y-m-d", "2023-08-15 extra", None, &tz)
+        else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(TIMELIB_ERR_TRAILING_DATA as i32, errors[0].code);
+        assert_eq!(10, errors[0].position);
+        assert_eq!(
+            Err(Error::InteriorNul(1)),
+            parse_from_format("Y\0", "2023", None, &tz)
+        );
+    }
+
     const SEC_PER_DAY: i64 = 86_400;
 
+    #[test]
+    fn add_sub_calendar_units() {
+        let tz = Timezone::parse("UTC").unwrap();
+        let month = Interval::new(0, 1, 0, 0, 0, 0);
+        // 2023-01-31 + 1 month overflows into March, like PHP.
+        assert_eq!(1677801600, add(1675123200, &month, &tz));
+        assert_eq!(1677801600, add_wall(1675123200, &month, &tz));
+        // 2023-03-31 - 1 month = 2023-02-31 = 2023-03-03.
+        assert_eq!(1677801600, sub(1680220800, &month, &tz));
+        assert_eq!(1677801600, sub_wall(1680220800, &month, &tz));
+        // 2024-02-29 + 1 year = 2025-03-01.
+        let year = Interval::new(1, 0, 0, 0, 0, 0);
+        assert_eq!(1740787200, add(1709164800, &year, &tz));
+    }
+
+    #[test]
+    fn add_sub_across_dst() {
+        let tz = Timezone::parse("America/Chicago").unwrap();
+        // 2024-03-09 12:00 CST + 1 day = 2024-03-10 12:00 CDT, 23 hours later.
+        let day = Interval::new(0, 0, 1, 0, 0, 0);
+        assert_eq!(1710090000, add(1710007200, &day, &tz));
+        assert_eq!(1710090000, add_wall(1710007200, &day, &tz));
+        assert_eq!(1710007200, sub(1710090000, &day, &tz));
+
+        // 2024-11-03 00:30 CDT + 1 elapsed hour = 01:30 CDT, + 2 = 01:30 CST.
+        let midnight = 1730611800;
+        let hour = Interval::new(0, 0, 0, 1, 0, 0);
+        let two_hours = Interval::new(0, 0, 0, 2, 0, 0);
+        assert_eq!(midnight + 3600, add_wall(midnight, &hour, &tz));
+        assert_eq!(midnight + 7200, add_wall(midnight, &two_hours, &tz));
+        assert_eq!(midnight, sub_wall(midnight + 7200, &two_hours, &tz));
+    }
+
+    #[test]
+    fn diff_units() {
+        let tz = Timezone::parse("UTC").unwrap();
+        // 2022-01-15 00:00:00 to 2023-03-20 10:30:15
+        let (one, two) = (1642204800, 1679308215);
+        let interval = diff(one, two, &tz);
+        assert_eq!(
+            (1, 2, 5, 10, 30, 15),
+            (
+                interval.years(),
+                interval.months(),
+                interval.days(),
+                interval.hours(),
+                interval.minutes(),
+                interval.seconds()
+            )
+        );
+        assert_eq!(Some(429), interval.total_days());
+        assert!(!interval.is_inverted());
+
+        let inverted = diff(two, one, &tz);
+        assert_eq!(2, inverted.months());
+        assert_eq!(Some(429), inverted.total_days());
+        assert!(inverted.is_inverted());
+        assert_eq!(429, diff_days(one, two, &tz));
+        assert_eq!(429, diff_days(two, one, &tz));
+    }
+
+    #[test]
+    fn diff_across_dst() {
+        let tz = Timezone::parse("America/Chicago").unwrap();
+        // 2024-03-09 12:00 CST to 2024-03-10 12:00 CDT
+        let interval = diff(1710007200, 1710090000, &tz);
+        assert_eq!((1, 0), (interval.days(), interval.hours()));
+        assert_eq!(1, diff_days(1710007200, 1710090000, &tz));
+        // The same instants are only 23 hours apart in UTC.
+        let utc = Timezone::parse("UTC").unwrap();
+        assert_eq!((0, 23), {
+            let interval = diff(1710007200, 1710090000, &utc);
+            (interval.days(), interval.hours())
+        });
+        assert_eq!(0, diff_days(1710007200, 1710090000, &utc));
+    }
+
+    #[test]
+    fn add_relative_weekday() {
+        let tz = Timezone::parse("UTC").unwrap();
+        let today = 1654318823; // Saturday, June 4, 2022 5:00:23 AM GMT
+        let next_tuesday = Interval::from_date_string("next tuesday").unwrap();
+        assert_eq!(today + 3 * SEC_PER_DAY, add(today, &next_tuesday, &tz));
+    }
+
     #[test]
     fn strtotime_valid_date_time_relative() {
         let tz = Timezone::parse("UTC").unwrap();
@@ -202,27 +794,4 @@ mod tests {
         let result = strtotime("tomorrow", Some(today), &tz);
         assert_eq!(Ok(tomorrow), result);
     }
-
-    #[test]
-    fn timezone_invalid_timezone() {
-        let result = Timezone::parse("pizza");
-        assert_eq!(Err("Invalid timezone. Err: 6.".to_string()), result);
-    }
-
-    #[test]
-    fn timezone_invalid_timezone_string() {
-        let result = Timezone::parse("UTC\0");
-        assert_eq!(Err("Malformed timezone string.".to_string()), result);
-    }
-
-    #[test]
-    fn timezone_valid_timezone() {
-        let result = Timezone::parse("America/Chicago");
-        assert!(result.is_ok());
-    }
-
-    #[test]
-    fn timezone_db_version() {
-        assert_eq!("2026.1", Timezone::db_version());
-    }
 }
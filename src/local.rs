@@ -0,0 +1,209 @@
+//! Detection of the local timezone, the way the C library does it.
+
+use std::{fs, io, path::Path};
+
+use crate::{Error, Timezone, TzError};
+
+const LOCALTIME: &str = "/etc/localtime";
+const TIMEZONE: &str = "/etc/timezone";
+const ZONEINFO: &str = "/usr/share/zoneinfo";
+
+/// Resolves the local timezone from the `TZ` environment variable, then the system configuration.
+pub(crate) fn local() -> Result<Timezone, Error> {
+    let tz = std::env::var("TZ").ok();
+    resolve(
+        tz.as_deref(),
+        Path::new(LOCALTIME),
+        Path::new(TIMEZONE),
+        Path::new(ZONEINFO),
+    )
+}
+
+fn resolve(
+    tz: Option<&str>,
+    localtime: &Path,
+    timezone: &Path,
+    zoneinfo: &Path,
+) -> Result<Timezone, Error> {
+    match tz {
+        // POSIX leaves an empty TZ implementation-defined; glibc and musl use UTC.
+        Some("") => Timezone::parse("UTC"),
+        Some(tz) => from_tz(tz, zoneinfo),
+        None => from_system(localtime, timezone),
+    }
+}
+
+/// Interprets a `TZ` value: ":path", an IANA name or a POSIX TZ string.
+fn from_tz(tz: &str, zoneinfo: &Path) -> Result<Timezone, Error> {
+    if let Some(path) = tz.strip_prefix(':') {
+        let path = Path::new(path);
+        if path.is_absolute() {
+            return from_file(path);
+        }
+        return Timezone::parse(&path.to_string_lossy())
+            .or_else(|_| from_file(&zoneinfo.join(path)));
+    }
+    Timezone::parse(tz)
+        .or_else(|_| from_file(&zoneinfo.join(tz)))
+        .or_else(|_| Timezone::from_posix(tz))
+}
+
+/// Reads `/etc/localtime` (preferring the name it links to) or `/etc/timezone`, defaulting to
+/// UTC if neither exists.
+fn from_system(localtime: &Path, timezone: &Path) -> Result<Timezone, Error> {
+    if let Some(tz) = fs::read_link(localtime)
+        .ok()
+        .and_then(|target| zone_name(&target))
+        .and_then(|name| Timezone::parse(&name).ok())
+    {
+        return Ok(tz);
+    }
+    if let Some(tz) = fs::read_to_string(timezone)
+        .ok()
+        .and_then(|name| Timezone::parse(name.trim()).ok())
+    {
+        return Ok(tz);
+    }
+    if localtime.exists() {
+        return from_file(localtime);
+    }
+    Timezone::parse("UTC")
+}
+
+/// Loads a TZif file, naming the timezone after its path inside the zoneinfo directory.
+fn from_file(path: &Path) -> Result<Timezone, Error> {
+    let name = zone_name(path).unwrap_or_else(|| path.to_string_lossy().into_owned());
+    let data = fs::read(path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => Error::Timezone(TzError::NoSuchTimezone),
+        _ => Error::InvalidTzif(name.clone()),
+    })?;
+    Timezone::from_tzif_bytes(&name, &data)
+}
+
+/// Extracts "America/Chicago" from paths such as "/usr/share/zoneinfo/America/Chicago" or
+/// "../usr/share/zoneinfo/posix/America/Chicago".
+fn zone_name(path: &Path) -> Option<String> {
+    let path = path.to_str()?;
+    let (_, name) = path.rsplit_once("zoneinfo/")?;
+    let name = ["posix/", "right/"]
+        .iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+        .unwrap_or(name);
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ops::Deref, path::PathBuf};
+
+    use super::*;
+    use crate::{strtotime, tzif::tests::tzif};
+
+    /// A fresh, empty directory that is removed when dropped.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir =
+                std::env::temp_dir().join(format!("timelib-local-{name}-{}", std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+    }
+
+    impl Deref for TempDir {
+        type Target = Path;
+
+        fn deref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn utc_offset(tz: &Timezone) -> i32 {
+        tz.offset_at(1654318800).utc_offset
+    }
+
+    #[test]
+    fn local_from_tz() {
+        let dir = TempDir::new("tz");
+        let missing = dir.join("missing");
+        let resolve = |tz| resolve(Some(tz), &missing, &missing, &dir);
+
+        assert_eq!(
+            "America/Chicago",
+            resolve("America/Chicago").unwrap().name()
+        );
+        assert_eq!(
+            "America/Chicago",
+            resolve(":America/Chicago").unwrap().name()
+        );
+        assert_eq!("UTC", resolve("").unwrap().name());
+        let posix = resolve("EST5EDT,M3.2.0,M11.1.0").unwrap();
+        assert_eq!(-14400, utc_offset(&posix));
+
+        let path = dir.join("Custom");
+        fs::write(&path, tzif(2, &[], &[(7200, false, "+02")], "<+02>-2")).unwrap();
+        let absolute = format!(":{}", path.display());
+        assert_eq!(7200, utc_offset(&resolve(&absolute).unwrap()));
+        assert_eq!(7200, utc_offset(&resolve(":Custom").unwrap()));
+        assert_eq!(7200, utc_offset(&resolve("Custom").unwrap()));
+        let absolute = format!(":{}", missing.display());
+        assert_eq!(
+            Err(Error::Timezone(TzError::NoSuchTimezone)),
+            resolve(&absolute)
+        );
+        let absolute = format!(":{}", dir.display());
+        assert_eq!(
+            Err(Error::InvalidTzif(dir.display().to_string())),
+            resolve(&absolute)
+        );
+        assert!(resolve(",,,").is_err());
+    }
+
+    #[test]
+    fn local_from_system() {
+        let dir = TempDir::new("system");
+        let localtime = dir.join("localtime");
+        let timezone = dir.join("timezone");
+        let resolve = || resolve(None, &localtime, &timezone, &dir);
+
+        assert_eq!("UTC", resolve().unwrap().name());
+
+        fs::write(&timezone, "Europe/London\n").unwrap();
+        assert_eq!("Europe/London", resolve().unwrap().name());
+
+        fs::write(&localtime, tzif(2, &[], &[(-7200, false, "-02")], "<-02>2")).unwrap();
+        assert_eq!("Europe/London", resolve().unwrap().name());
+        fs::remove_file(&timezone).unwrap();
+        let tz = resolve().unwrap();
+        assert_eq!(-7200, utc_offset(&tz));
+        assert_eq!(Ok(1654308000), strtotime("2022-06-04 00:00:00", None, &tz));
+
+        #[cfg(unix)]
+        {
+            fs::remove_file(&localtime).unwrap();
+            std::os::unix::fs::symlink("/usr/share/zoneinfo/Asia/Tokyo", &localtime).unwrap();
+            assert_eq!("Asia/Tokyo", resolve().unwrap().name());
+        }
+    }
+
+    #[test]
+    fn local_zone_name() {
+        assert_eq!(
+            Some("America/Chicago".to_string()),
+            zone_name(Path::new("/usr/share/zoneinfo/America/Chicago"))
+        );
+        assert_eq!(
+            Some("Europe/Paris".to_string()),
+            zone_name(Path::new("../usr/share/zoneinfo/posix/Europe/Paris"))
+        );
+        assert_eq!(None, zone_name(Path::new("/etc/localtime")));
+    }
+}
@@ -0,0 +1,311 @@
+use std::{ffi::CStr, sync::Arc};
+
+use crate::{
+    internal::*, rust_now_sec, timezone::TzInfo, Interval, ParseWarning, Timestamp, Timezone,
+};
+
+/// The result of parsing a date/time string, before any missing fields are filled in.
+///
+/// Fields that were not present in the input are returned as `None`.
+#[derive(Debug)]
+pub struct ParsedTime {
+    time: *mut timelib_time,
+    warnings: Vec<ParseWarning>,
+    /// Keeps alive the timezones `time` points to, even if they are evicted from the cache.
+    _timezones: Vec<Arc<TzInfo>>,
+}
+
+impl Drop for ParsedTime {
+    fn drop(&mut self) {
+        unsafe {
+            timelib_time_dtor(self.time);
+        }
+    }
+}
+
+impl ParsedTime {
+    /// Takes ownership of a `timelib_time` returned by timelib's parser.
+    pub(crate) fn new(
+        time: *mut timelib_time,
+        warnings: Vec<ParseWarning>,
+        timezones: Vec<Arc<TzInfo>>,
+    ) -> Self {
+        Self {
+            time,
+            warnings,
+            _timezones: timezones,
+        }
+    }
+
+    fn raw(&self) -> &timelib_time {
+        unsafe { &*self.time }
+    }
+
+    /// Returns the year, if one was parsed.
+    pub fn year(&self) -> Option<i64> {
+        unset_to_none(self.raw().y)
+    }
+
+    /// Returns the month (1-12), if one was parsed.
+    pub fn month(&self) -> Option<i64> {
+        unset_to_none(self.raw().m)
+    }
+
+    /// Returns the day of the month, if one was parsed.
+    pub fn day(&self) -> Option<i64> {
+        unset_to_none(self.raw().d)
+    }
+
+    /// Returns the hour, if one was parsed.
+    pub fn hour(&self) -> Option<i64> {
+        unset_to_none(self.raw().h)
+    }
+
+    /// Returns the minute, if one was parsed.
+    pub fn minute(&self) -> Option<i64> {
+        unset_to_none(self.raw().i)
+    }
+
+    /// Returns the second, if one was parsed.
+    pub fn second(&self) -> Option<i64> {
+        unset_to_none(self.raw().s)
+    }
+
+    /// Returns the microsecond, if one was parsed.
+    pub fn microsecond(&self) -> Option<i64> {
+        unset_to_none(self.raw().us)
+    }
+
+    /// Returns whether the input contained a date.
+    pub fn has_date(&self) -> bool {
+        self.raw().have_date != 0
+    }
+
+    /// Returns whether the input contained a time.
+    pub fn has_time(&self) -> bool {
+        self.raw().have_time != 0
+    }
+
+    /// Returns whether the input contained a timezone.
+    pub fn has_zone(&self) -> bool {
+        self.raw().have_zone != 0
+    }
+
+    /// Returns whether the input contained a relative component, e.g. "+1 day" or "next tuesday".
+    pub fn has_relative(&self) -> bool {
+        self.raw().have_relative != 0
+    }
+
+    /// Returns the relative component, if the input contained one.
+    pub fn relative(&self) -> Option<Interval> {
+        self.has_relative()
+            .then(|| Interval::from_raw(&self.raw().relative))
+    }
+
+    /// Returns the UTC offset in seconds, if the input contained an offset or a timezone
+    /// abbreviation, e.g. "+05:30" or "CEST". The offset of an abbreviation includes any DST hour.
+    pub fn utc_offset(&self) -> Option<i32> {
+        let raw = self.raw();
+        if raw.have_zone == 0 {
+            return None;
+        }
+        match raw.zone_type {
+            TIMELIB_ZONETYPE_OFFSET => Some(raw.z),
+            // timelib keeps the DST hour out of the offset of abbreviations.
+            TIMELIB_ZONETYPE_ABBR => Some(raw.z + raw.dst * 3600),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the parsed timezone abbreviation denotes daylight saving time.
+    pub fn is_dst(&self) -> Option<bool> {
+        let raw = self.raw();
+        (raw.have_zone != 0 && raw.zone_type == TIMELIB_ZONETYPE_ABBR).then_some(raw.dst != 0)
+    }
+
+    /// Returns the timezone abbreviation, if the input contained one, e.g. "CEST".
+    pub fn timezone_abbreviation(&self) -> Option<String> {
+        let raw = self.raw();
+        if raw.have_zone == 0 || raw.zone_type != TIMELIB_ZONETYPE_ABBR || raw.tz_abbr.is_null() {
+            return None;
+        }
+        Some(
+            unsafe { CStr::from_ptr(raw.tz_abbr) }
+                .to_string_lossy()
+                .into_owned(),
+        )
+    }
+
+    /// Returns the timezone identifier, if the input contained one, e.g. "America/New_York".
+    pub fn timezone_id(&self) -> Option<String> {
+        let raw = self.raw();
+        if raw.have_zone == 0 || raw.zone_type != TIMELIB_ZONETYPE_ID || raw.tz_info.is_null() {
+            return None;
+        }
+        Some(
+            unsafe { CStr::from_ptr((*raw.tz_info).name) }
+                .to_string_lossy()
+                .into_owned(),
+        )
+    }
+
+    /// Returns the warnings timelib reported while parsing.
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+
+    /// Returns a timestamp (in seconds since the epoch), filling in any missing fields from the
+    /// base timestamp in the given timezone.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_timestamp` - An optional timestamp (in seconds) to use as your base (defaults to the current timestamp).
+    /// * `timezone` - An address of a Timezone object.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let tz = timelib::Timezone::parse("UTC").expect("Error parsing timezone!");
+    /// let parsed = timelib::parse("tomorrow").expect("Error parsing date!");
+    /// assert_eq!(1654387200, parsed.to_timestamp(Some(1654318823), &tz));
+    /// ```
+    pub fn to_timestamp(&self, base_timestamp: Option<i64>, timezone: &Timezone) -> i64 {
+        self.to_timestamp_precise(base_timestamp, timezone).seconds
+    }
+
+    /// Returns a timestamp with microsecond precision, filling in any missing fields from the
+    /// base timestamp in the given timezone.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_timestamp` - An optional timestamp (in seconds) to use as your base (defaults to the current timestamp).
+    /// * `timezone` - An address of a Timezone object.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let tz = timelib::Timezone::parse("UTC").expect("Error parsing timezone!");
+    /// let parsed = timelib::parse("2024-01-01 12:00:00.123456").expect("Error parsing date!");
+    /// let timestamp = parsed.to_timestamp_precise(None, &tz);
+    /// assert_eq!(1704110400, timestamp.seconds);
+    /// assert_eq!(123456, timestamp.microseconds);
+    /// ```
+    pub fn to_timestamp_precise(
+        &self,
+        base_timestamp: Option<i64>,
+        timezone: &Timezone,
+    ) -> Timestamp {
+        self.resolve(base_timestamp, timezone, TIMELIB_NO_CLONE)
+    }
+
+    /// Fills in any missing fields from the base timestamp with the given `timelib_fill_holes`
+    /// options and returns the resulting timestamp.
+    pub(crate) fn resolve(
+        &self,
+        base_timestamp: Option<i64>,
+        timezone: &Timezone,
+        options: u32,
+    ) -> Timestamp {
+        unsafe {
+            let parsed_time = timelib_time_clone(self.time);
+            let base = timezone.local_time(base_timestamp.unwrap_or_else(rust_now_sec));
+
+            timelib_fill_holes(parsed_time, base, options as i32);
+            timelib_update_ts(parsed_time, timezone.tzi());
+            let result = Timestamp {
+                seconds: (*parsed_time).sse,
+                microseconds: (*parsed_time).us as u32,
+            };
+            timelib_time_dtor(parsed_time);
+            timelib_time_dtor(base);
+
+            result
+        }
+    }
+}
+
+fn unset_to_none(value: timelib_sll) -> Option<i64> {
+    (value != TIMELIB_UNSET as timelib_sll).then_some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse, Timestamp, Timezone};
+
+    #[test]
+    fn parsed_time_date() {
+        let parsed = parse("2024-01-02").unwrap();
+        assert!(parsed.has_date());
+        assert!(!parsed.has_time());
+        assert!(!parsed.has_zone());
+        assert!(!parsed.has_relative());
+        assert_eq!(Some(2024), parsed.year());
+        assert_eq!(Some(1), parsed.month());
+        assert_eq!(Some(2), parsed.day());
+        assert_eq!(None, parsed.hour());
+        assert_eq!(None, parsed.minute());
+        assert_eq!(None, parsed.second());
+        assert!(parsed.relative().is_none());
+    }
+
+    #[test]
+    fn parsed_time_relative() {
+        let parsed = parse("next tuesday").unwrap();
+        assert!(!parsed.has_date());
+        assert!(parsed.has_relative());
+        assert_eq!(None, parsed.year());
+        assert_eq!(Some(2), parsed.relative().unwrap().weekday());
+    }
+
+    #[test]
+    fn parsed_time_date_time_offset() {
+        let parsed = parse("2024-01-02 10:30:15.5 +05:30").unwrap();
+        assert!(parsed.has_date());
+        assert!(parsed.has_time());
+        assert!(parsed.has_zone());
+        assert_eq!(Some(10), parsed.hour());
+        assert_eq!(Some(30), parsed.minute());
+        assert_eq!(Some(15), parsed.second());
+        assert_eq!(Some(500000), parsed.microsecond());
+        assert_eq!(Some(19800), parsed.utc_offset());
+        assert_eq!(None, parsed.timezone_id());
+    }
+
+    #[test]
+    fn parsed_time_abbreviation() {
+        let parsed = parse("2024-07-01 12:00 CEST").unwrap();
+        assert_eq!(Some("CEST".to_string()), parsed.timezone_abbreviation());
+        assert_eq!(Some(true), parsed.is_dst());
+        assert_eq!(Some(7200), parsed.utc_offset());
+        let parsed = parse("2024-01-01 12:00 CET").unwrap();
+        assert_eq!(Some(false), parsed.is_dst());
+        assert_eq!(Some(3600), parsed.utc_offset());
+    }
+
+    #[test]
+    fn parsed_time_timezone_id() {
+        let parsed = parse("2006-05-12 13:00:00 America/New_York").unwrap();
+        assert_eq!(Some("America/New_York".to_string()), parsed.timezone_id());
+        assert_eq!(None, parsed.utc_offset());
+    }
+
+    #[test]
+    fn parsed_time_to_timestamp_precise() {
+        let tz = Timezone::parse("America/Chicago").unwrap();
+        let parsed = parse("2022-06-04 00:00:00.000250").unwrap();
+        assert_eq!(
+            Timestamp {
+                seconds: 1654318800,
+                microseconds: 250,
+            },
+            parsed.to_timestamp_precise(None, &tz)
+        );
+        assert_eq!(1654318800, parsed.to_timestamp(None, &tz));
+    }
+
+    #[test]
+    fn parsed_time_warnings() {
+        let parsed = parse("2023-02-30").unwrap();
+        assert_eq!(1, parsed.warnings().len());
+    }
+}
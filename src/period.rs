@@ -0,0 +1,257 @@
+use crate::{internal::*, Error, Interval, IsoInterval, Timezone};
+
+/// When a [`Period`] stops.
+#[derive(Debug, Clone, Copy)]
+enum Bound {
+    /// Stop before `end`, or after it if `inclusive`.
+    End { end: i64, inclusive: bool },
+    /// Stop after this many occurrences.
+    Count(u32),
+}
+
+/// An iterator over the occurrences of a recurring interval, like PHP's `DatePeriod`.
+///
+/// Occurrences are computed lazily by repeatedly adding the interval to the previous occurrence
+/// in the period's timezone, so that calendar units follow PHP's rules: a monthly period starting
+/// on January 31st continues on March 2nd (in a leap year), April 2nd and so on.
+///
+/// # Examples
+///
+/// ```
+/// let tz = timelib::Timezone::parse("UTC").expect("Error parsing timezone!");
+/// let fortnight = timelib::Interval::from_date_string("+2 weeks").unwrap();
+/// // Every second Tuesday from 2024-01-02 until February.
+/// let period = timelib::Period::until(1704153600, fortnight, 1706745600, &tz);
+/// let tuesdays: Vec<i64> = period.collect();
+/// assert_eq!(vec![1704153600, 1705363200, 1706572800], tuesdays);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Period {
+    timezone: Timezone,
+    interval: Interval,
+    /// The next occurrence, or `None` once the period is exhausted.
+    current: Option<i64>,
+    bound: Bound,
+    /// The number of occurrences yielded so far.
+    count: u32,
+    start_excluded: bool,
+}
+
+impl Period {
+    /// Returns the occurrences from `start` up to, but not including, `end`.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The first occurrence (in seconds since the epoch).
+    /// * `interval` - The interval between occurrences.
+    /// * `end` - The timestamp (in seconds) to stop at.
+    /// * `timezone` - An address of a Timezone object.
+    pub fn until(start: i64, interval: Interval, end: i64, timezone: &Timezone) -> Self {
+        Self::new(
+            start,
+            interval,
+            Bound::End {
+                end,
+                inclusive: false,
+            },
+            timezone,
+        )
+    }
+
+    /// Returns `start` followed by `recurrences` further occurrences.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The first occurrence (in seconds since the epoch).
+    /// * `interval` - The interval between occurrences.
+    /// * `recurrences` - The number of occurrences after `start`.
+    /// * `timezone` - An address of a Timezone object.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let tz = timelib::Timezone::parse("UTC").expect("Error parsing timezone!");
+    /// let day = timelib::Interval::new(0, 0, 1, 0, 0, 0);
+    /// assert_eq!(3, timelib::Period::recurring(1704153600, day, 2, &tz).count());
+    /// ```
+    pub fn recurring(
+        start: i64,
+        interval: Interval,
+        recurrences: u32,
+        timezone: &Timezone,
+    ) -> Self {
+        Self::new(
+            start,
+            interval,
+            Bound::Count(recurrences.saturating_add(1)),
+            timezone,
+        )
+    }
+
+    /// Returns the occurrences of an ISO 8601 interval with a start, a period and either an end or
+    /// a recurrence count, such as "R4/2012-07-01T00:00:00Z/P7D", or an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - A string that holds the ISO 8601 interval.
+    /// * `timezone` - An address of a Timezone object to add the period in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let tz = timelib::Timezone::parse("UTC").expect("Error parsing timezone!");
+    /// let weekly = timelib::Period::from_iso("R4/2012-07-01T00:00:00Z/P7D", &tz).unwrap();
+    /// assert_eq!(5, weekly.count());
+    /// ```
+    pub fn from_iso(interval: &str, timezone: &Timezone) -> Result<Self, Error> {
+        match IsoInterval::parse(interval)? {
+            IsoInterval {
+                start: Some(start),
+                period: Some(period),
+                end: Some(end),
+                ..
+            } => Ok(Self::until(start, period, end, timezone)),
+            IsoInterval {
+                start: Some(start),
+                period: Some(period),
+                recurrences: Some(recurrences),
+                ..
+            } if recurrences > 0 => Ok(Self::recurring(start, period, recurrences, timezone)),
+            _ => Err(Error::InvalidInterval(interval.into())),
+        }
+    }
+
+    fn new(start: i64, interval: Interval, bound: Bound, timezone: &Timezone) -> Self {
+        Self {
+            timezone: timezone.clone(),
+            interval,
+            current: Some(start),
+            bound,
+            count: 0,
+            start_excluded: false,
+        }
+    }
+
+    /// Skips the start, like PHP's `DatePeriod::EXCLUDE_START_DATE`. A recurring period still
+    /// yields its `recurrences` occurrences after the start.
+    pub fn exclude_start(mut self) -> Self {
+        if !self.start_excluded && self.count == 0 {
+            self.start_excluded = true;
+            self.current = self.current.and_then(|current| self.advance(current));
+            if let Bound::Count(count) = &mut self.bound {
+                *count -= 1;
+            }
+        }
+        self
+    }
+
+    /// Includes an occurrence that falls exactly on the end, like PHP's
+    /// `DatePeriod::INCLUDE_END_DATE`. Has no effect on recurring periods.
+    pub fn include_end(mut self) -> Self {
+        if let Bound::End { inclusive, .. } = &mut self.bound {
+            *inclusive = true;
+        }
+        self
+    }
+
+    /// Returns the occurrence after `current`, or `None` if an end-bounded period would never
+    /// reach its end.
+    fn advance(&self, current: i64) -> Option<i64> {
+        let next = self.interval.apply(current, &self.timezone, timelib_add);
+        match self.bound {
+            Bound::End { .. } if next <= current => None,
+            _ => Some(next),
+        }
+    }
+}
+
+impl Iterator for Period {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<i64> {
+        let current = self.current?;
+        let valid = match self.bound {
+            Bound::End { end, inclusive } => current < end || (inclusive && current == end),
+            Bound::Count(count) => self.count < count,
+        };
+        if !valid {
+            self.current = None;
+            return None;
+        }
+        self.count += 1;
+        self.current = self.advance(current);
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WEEK: i64 = 7 * 86400;
+
+    #[test]
+    fn period_recurring() {
+        let tz = Timezone::parse("UTC").unwrap();
+        let start = 1341100800; // 2012-07-01
+        let weekly = Period::from_iso("R4/2012-07-01T00:00:00Z/P7D", &tz).unwrap();
+        assert_eq!(
+            (0..=4).map(|week| start + week * WEEK).collect::<Vec<_>>(),
+            weekly.clone().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            (1..=4).map(|week| start + week * WEEK).collect::<Vec<_>>(),
+            weekly.exclude_start().exclude_start().collect::<Vec<_>>()
+        );
+        let none = Period::recurring(start, Interval::new(0, 0, 7, 0, 0, 0), 0, &tz);
+        assert_eq!(vec![start], none.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn period_until() {
+        let tz = Timezone::parse("UTC").unwrap();
+        let month = Interval::new(0, 1, 0, 0, 0, 0);
+        // From 2024-01-31, monthly until 2024-05-02; each step adds to the previous one.
+        let monthly = Period::until(1706659200, month, 1714608000, &tz);
+        assert_eq!(
+            vec![1706659200, 1709337600, 1712016000],
+            monthly.clone().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![1709337600, 1712016000, 1714608000],
+            monthly.exclude_start().include_end().collect::<Vec<_>>()
+        );
+        let never = Period::until(1706659200, Interval::new(0, 0, 0, 0, 0, 0), 1714608000, &tz);
+        assert_eq!(vec![1706659200], never.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn period_across_dst() {
+        let tz = Timezone::parse("America/Chicago").unwrap();
+        // Daily at noon from 2024-03-09, the day before DST starts.
+        let day = Interval::new(0, 0, 1, 0, 0, 0);
+        assert_eq!(
+            vec![1710007200, 1710090000, 1710176400],
+            Period::recurring(1710007200, day, 2, &tz).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn period_from_iso_errors() {
+        let tz = Timezone::parse("UTC").unwrap();
+        for interval in [
+            "P1D",
+            "2012-07-01T00:00:00Z/P7D",
+            "R0/2012-07-01T00:00:00Z/P7D",
+        ] {
+            assert!(matches!(
+                Period::from_iso(interval, &tz),
+                Err(Error::InvalidInterval(_))
+            ));
+        }
+        assert!(matches!(
+            Period::from_iso("R4/every week", &tz),
+            Err(Error::Parse(_))
+        ));
+    }
+}
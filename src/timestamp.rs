@@ -0,0 +1,63 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A timestamp with microsecond precision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp {
+    /// Whole seconds since the epoch.
+    pub seconds: i64,
+    /// Microseconds past `seconds` (0-999999).
+    pub microseconds: u32,
+}
+
+impl From<Timestamp> for SystemTime {
+    fn from(timestamp: Timestamp) -> Self {
+        let micros = Duration::from_micros(timestamp.microseconds.into());
+        if timestamp.seconds >= 0 {
+            UNIX_EPOCH + Duration::from_secs(timestamp.seconds as u64) + micros
+        } else {
+            UNIX_EPOCH - Duration::from_secs(timestamp.seconds.unsigned_abs()) + micros
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamp_to_system_time() {
+        let timestamp = Timestamp {
+            seconds: 1704110400,
+            microseconds: 123456,
+        };
+        assert_eq!(
+            UNIX_EPOCH + Duration::new(1704110400, 123_456_000),
+            SystemTime::from(timestamp)
+        );
+    }
+
+    #[test]
+    fn timestamp_to_system_time_before_epoch() {
+        let timestamp = Timestamp {
+            seconds: -2,
+            microseconds: 500000,
+        };
+        assert_eq!(
+            UNIX_EPOCH - Duration::from_millis(1500),
+            SystemTime::from(timestamp)
+        );
+    }
+
+    #[test]
+    fn timestamp_ordering() {
+        let earlier = Timestamp {
+            seconds: 10,
+            microseconds: 999999,
+        };
+        let later = Timestamp {
+            seconds: 11,
+            microseconds: 0,
+        };
+        assert!(earlier < later);
+    }
+}
@@ -0,0 +1,792 @@
+use std::{
+    ffi::{CStr, CString},
+    ops::Range,
+    ptr,
+    sync::Arc,
+};
+
+use crate::{
+    internal::*, local, tzif, Abbreviation, Error, Transitions, TzDb, TzError, ZoneOffset,
+};
+
+/// Owns a parsed `timelib_tzinfo` and frees it on drop.
+#[derive(Debug)]
+pub(crate) struct TzInfo(*mut timelib_tzinfo);
+
+// timelib never mutates a timezone after parsing it, so it can be read from any thread.
+unsafe impl Send for TzInfo {}
+unsafe impl Sync for TzInfo {}
+
+impl TzInfo {
+    /// Takes ownership of a non-null `timelib_tzinfo`.
+    pub(crate) fn new(tzi: *mut timelib_tzinfo) -> Self {
+        Self(tzi)
+    }
+
+    pub(crate) fn as_ptr(&self) -> *mut timelib_tzinfo {
+        self.0
+    }
+}
+
+impl Drop for TzInfo {
+    fn drop(&mut self) {
+        unsafe {
+            timelib_tzinfo_dtor(self.0);
+        }
+    }
+}
+
+/// A Timezone wrapper: a timezone from a database such as "America/Chicago", a fixed UTC offset
+/// such as "+05:30", or an abbreviation such as "PST".
+///
+/// Cloning is cheap: clones share the same underlying timezone data.
+#[derive(Debug, Clone)]
+pub struct Timezone {
+    kind: Kind,
+}
+
+#[derive(Debug, Clone)]
+enum Kind {
+    /// A timezone loaded from a database (`TIMELIB_ZONETYPE_ID`).
+    Id { tzi: Arc<TzInfo>, slim: bool },
+    /// A fixed offset in seconds east of UTC (`TIMELIB_ZONETYPE_OFFSET`).
+    Offset(i32),
+    /// An abbreviation along with its offset in seconds east of UTC, DST included
+    /// (`TIMELIB_ZONETYPE_ABBR`).
+    Abbr {
+        abbreviation: CString,
+        utc_offset: i32,
+        is_dst: bool,
+    },
+}
+
+impl PartialEq for Timezone {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.kind, &other.kind) {
+            (Kind::Id { tzi: a, .. }, Kind::Id { tzi: b, .. }) => Arc::ptr_eq(a, b),
+            (Kind::Offset(a), Kind::Offset(b)) => a == b,
+            (
+                Kind::Abbr {
+                    abbreviation: a,
+                    utc_offset: a_offset,
+                    is_dst: a_dst,
+                },
+                Kind::Abbr {
+                    abbreviation: b,
+                    utc_offset: b_offset,
+                    is_dst: b_dst,
+                },
+            ) => a == b && a_offset == b_offset && a_dst == b_dst,
+            _ => false,
+        }
+    }
+}
+
+impl Timezone {
+    /// Parses a String into a Timezone instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `timezone` - A String with your IANA Timezone name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let tz = timelib::Timezone::parse("UTC");
+    /// let tz = timelib::Timezone::parse("America/Chicago");
+    /// ```
+    pub fn parse(timezone: &str) -> Result<Timezone, Error> {
+        Self::parse_with_db(timezone, &TzDb::builtin())
+    }
+
+    /// Parses a String into a Timezone instance using the given timezone database.
+    ///
+    /// # Arguments
+    ///
+    /// * `timezone` - A String with your IANA Timezone name.
+    /// * `db` - The timezone database to look the name up in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let db = timelib::TzDb::builtin();
+    /// let tz = timelib::Timezone::parse_with_db("America/Chicago", &db);
+    /// ```
+    pub fn parse_with_db(timezone: &str, db: &TzDb) -> Result<Timezone, Error> {
+        let tz_c_str = CString::new(timezone).map_err(|e| Error::InteriorNul(e.nul_position()))?;
+        let mut error_code: i32 = 0;
+        let error_code_ptr = &mut error_code as *mut i32;
+        unsafe {
+            let tzi = timelib_parse_tzfile(tz_c_str.as_ptr(), db.as_ptr(), error_code_ptr);
+            if tzi.is_null() {
+                return Err(TzError::from_code(error_code).into());
+            }
+            Ok(Self {
+                kind: Kind::Id {
+                    tzi: Arc::new(TzInfo::new(tzi)),
+                    slim: error_code == TIMELIB_ERROR_SLIM_FILE as i32,
+                },
+            })
+        }
+    }
+
+    /// Creates a Timezone with a fixed offset from UTC, without a database lookup.
+    ///
+    /// # Arguments
+    ///
+    /// * `utc_offset` - The offset in seconds east of UTC.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let tz = timelib::Timezone::from_offset(19800);
+    /// assert_eq!(Ok(1654281000), timelib::strtotime("2022-06-04 00:00:00", None, &tz));
+    /// ```
+    pub fn from_offset(utc_offset: i32) -> Timezone {
+        Self {
+            kind: Kind::Offset(utc_offset),
+        }
+    }
+
+    /// Parses a UTC offset such as "+05:30", "-0800", "+5" or "UTC+05:30" into a Timezone.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The offset, which must start with a sign (optionally after "UTC" or "GMT").
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let tz = timelib::Timezone::parse_offset("+05:30").expect("Error parsing offset!");
+    /// assert_eq!(timelib::Timezone::from_offset(19800), tz);
+    /// ```
+    pub fn parse_offset(offset: &str) -> Result<Timezone, Error> {
+        parse_offset_seconds(offset)
+            .map(Self::from_offset)
+            .ok_or_else(|| Error::InvalidOffset(offset.into()))
+    }
+
+    /// Creates a Timezone from an abbreviation and the offset it stands for, without a
+    /// database lookup.
+    ///
+    /// # Arguments
+    ///
+    /// * `abbreviation` - The abbreviation, e.g. "CEST".
+    /// * `utc_offset` - The offset in seconds east of UTC, including DST, e.g. 7200 for CEST.
+    /// * `is_dst` - Whether the abbreviation denotes daylight saving time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let tz = timelib::Timezone::from_abbreviation("CEST", 7200, true).expect("Error!");
+    /// ```
+    pub fn from_abbreviation(
+        abbreviation: &str,
+        utc_offset: i32,
+        is_dst: bool,
+    ) -> Result<Timezone, Error> {
+        let abbreviation = CString::new(abbreviation.to_ascii_uppercase())
+            .map_err(|e| Error::InteriorNul(e.nul_position()))?;
+        Ok(Self {
+            kind: Kind::Abbr {
+                abbreviation,
+                utc_offset,
+                is_dst,
+            },
+        })
+    }
+
+    /// Parses an abbreviation such as "PST" into a Timezone, using the offset timelib associates
+    /// with it when parsing date/time strings. See [`Abbreviation`] for the candidates.
+    ///
+    /// # Arguments
+    ///
+    /// * `abbreviation` - The abbreviation, compared case-insensitively.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let tz = timelib::Timezone::parse_abbreviation("PST").expect("Error parsing abbreviation!");
+    /// assert_eq!(Ok(1654329600), timelib::strtotime("2022-06-04 00:00:00", None, &tz));
+    /// ```
+    pub fn parse_abbreviation(abbreviation: &str) -> Result<Timezone, Error> {
+        if abbreviation.eq_ignore_ascii_case("UTC") || abbreviation.eq_ignore_ascii_case("GMT") {
+            return Self::from_abbreviation(abbreviation, 0, false);
+        }
+        let entry = Abbreviation::lookup(abbreviation)
+            .into_iter()
+            .next()
+            .ok_or(Error::Timezone(TzError::NoSuchTimezone))?;
+        Self::from_abbreviation(&entry.abbreviation, entry.utc_offset, entry.is_dst)
+    }
+
+    /// Returns the timezone's name: its identifier (e.g. "America/Chicago"), its offset (e.g.
+    /// "+05:30") or its abbreviation (e.g. "PST").
+    pub fn name(&self) -> String {
+        match &self.kind {
+            Kind::Id { tzi, .. } => unsafe { CStr::from_ptr((*tzi.as_ptr()).name) }
+                .to_string_lossy()
+                .into_owned(),
+            Kind::Offset(utc_offset) => format_offset(*utc_offset),
+            Kind::Abbr { abbreviation, .. } => abbreviation.to_string_lossy().into_owned(),
+        }
+    }
+
+    /// Creates a Timezone from TZif data (versions 1 to 4), e.g. the contents of a file from a
+    /// zoneinfo directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name to give the timezone.
+    /// * `data` - The TZif data.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// let data = std::fs::read("/usr/share/zoneinfo/America/Chicago").unwrap();
+    /// let tz = timelib::Timezone::from_tzif_bytes("America/Chicago", &data);
+    /// ```
+    pub fn from_tzif_bytes(name: &str, data: &[u8]) -> Result<Timezone, Error> {
+        Self::parse_with_db(name, &TzDb::from_tzif("", [(name, data)])?)
+    }
+
+    /// Creates a Timezone from a POSIX TZ string such as "EST5EDT,M3.2.0,M11.1.0", as found in
+    /// the `TZ` environment variable.
+    ///
+    /// # Arguments
+    ///
+    /// * `posix` - The POSIX TZ string, which also becomes the timezone's name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let tz = timelib::Timezone::from_posix("EST5EDT,M3.2.0,M11.1.0").expect("Error parsing TZ!");
+    /// assert_eq!("EDT", tz.offset_at(1654318800).abbreviation);
+    /// ```
+    pub fn from_posix(posix: &str) -> Result<Timezone, Error> {
+        if posix.is_empty() {
+            return Err(TzError::EmptyPosixString.into());
+        }
+        let posix_c_str = CString::new(posix).map_err(|e| Error::InteriorNul(e.nul_position()))?;
+        let mut types = Vec::new();
+        unsafe {
+            let ps = timelib_parse_posix_str(posix_c_str.as_ptr());
+            if ps.is_null() {
+                return Err(TzError::CorruptPosixString.into());
+            }
+            let raw = &*ps;
+            for (abbr, offset, is_dst) in [
+                (raw.std, raw.std_offset, false),
+                (raw.dst, raw.dst_offset, true),
+            ] {
+                if !abbr.is_null() {
+                    types.push((
+                        offset as i32,
+                        is_dst,
+                        CStr::from_ptr(abbr).to_bytes().to_vec(),
+                    ));
+                }
+            }
+            timelib_posix_str_dtor(ps);
+        }
+        let types: Vec<_> = types
+            .iter()
+            .map(|(offset, is_dst, abbr)| (*offset, *is_dst, abbr.as_slice()))
+            .collect();
+        let data = tzif::without_transitions(&types, posix.as_bytes());
+        Self::from_tzif_bytes(posix, &data)
+    }
+
+    /// Returns the local timezone of the machine.
+    ///
+    /// Like the C library, this honors the `TZ` environment variable first, in any of its forms:
+    /// an IANA name, a POSIX TZ string or ":path" to a TZif file. Without `TZ`, the timezone is
+    /// read from `/etc/localtime` or `/etc/timezone`, and defaults to UTC if neither exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let tz = timelib::Timezone::local().expect("Error detecting local timezone!");
+    /// timelib::strtotime("tomorrow", None, &tz);
+    /// ```
+    pub fn local() -> Result<Timezone, Error> {
+        local::local()
+    }
+
+    /// Returns whether the timezone was loaded from a "slim" TZif file, which leaves out the
+    /// transitions that can be computed from its POSIX TZ string.
+    pub fn is_slim(&self) -> bool {
+        matches!(self.kind, Kind::Id { slim: true, .. })
+    }
+
+    /// Returns the underlying timezone database version.
+    pub fn db_version() -> String {
+        let cstr = unsafe { CStr::from_ptr((*timelib_builtin_db()).version) };
+        String::from_utf8_lossy(cstr.to_bytes()).to_string()
+    }
+
+    /// Returns the UTC offset, DST flag and abbreviation in effect at the given instant.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp` - The instant, in seconds since the epoch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let tz = timelib::Timezone::parse("America/Chicago").expect("Error parsing timezone!");
+    /// let offset = tz.offset_at(1654318800);
+    /// assert_eq!("CDT (UTC-05:00)", offset.to_string());
+    /// ```
+    pub fn offset_at(&self, timestamp: i64) -> ZoneOffset {
+        match &self.kind {
+            Kind::Id { tzi, .. } => unsafe {
+                let offset = timelib_get_time_zone_info(timestamp, tzi.as_ptr());
+                let zone_offset = ZoneOffset::from_raw(&*offset);
+                timelib_time_offset_dtor(offset);
+                zone_offset
+            },
+            Kind::Offset(utc_offset) => ZoneOffset {
+                utc_offset: *utc_offset,
+                is_dst: false,
+                abbreviation: format_offset(*utc_offset),
+                transition_time: 0,
+            },
+            Kind::Abbr {
+                abbreviation,
+                utc_offset,
+                is_dst,
+            } => ZoneOffset {
+                utc_offset: *utc_offset,
+                is_dst: *is_dst,
+                abbreviation: abbreviation.to_string_lossy().into_owned(),
+                transition_time: 0,
+            },
+        }
+    }
+
+    /// Returns the ISO 3166-1 alpha-2 code of the country the timezone is in, e.g. "US", or `None`
+    /// if it isn't tied to a country. Only the bundled database records countries.
+    pub fn country_code(&self) -> Option<String> {
+        let tzi = self.tzi();
+        if tzi.is_null() {
+            return None;
+        }
+        let code = unsafe { CStr::from_ptr((*tzi).location.country_code.as_ptr()) };
+        let code = code.to_string_lossy();
+        (!code.is_empty() && code != "??").then(|| code.into_owned())
+    }
+
+    /// Returns the latitude of the timezone's principal location in degrees (north is positive),
+    /// or `None` if it isn't tied to a country.
+    pub fn latitude(&self) -> Option<f64> {
+        self.country_code()
+            .map(|_| unsafe { (*self.tzi()).location.latitude })
+    }
+
+    /// Returns the longitude of the timezone's principal location in degrees (east is positive),
+    /// or `None` if it isn't tied to a country.
+    pub fn longitude(&self) -> Option<f64> {
+        self.country_code()
+            .map(|_| unsafe { (*self.tzi()).location.longitude })
+    }
+
+    /// Returns the comments from the timezone's entry in `zone.tab`, such as
+    /// "Central (most areas)", or `None` if there are none.
+    pub fn comments(&self) -> Option<String> {
+        let tzi = self.tzi();
+        if tzi.is_null() {
+            return None;
+        }
+        let comments = unsafe { (*tzi).location.comments };
+        if comments.is_null() {
+            return None;
+        }
+        let comments = unsafe { CStr::from_ptr(comments) }.to_string_lossy();
+        (!comments.is_empty()).then(|| comments.into_owned())
+    }
+
+    /// Returns an iterator over the timezone's transitions within the given range of timestamps,
+    /// including future ones derived from the timezone's POSIX TZ string.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The timestamps (in seconds since the epoch) to find transitions in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let tz = timelib::Timezone::parse("America/Chicago").expect("Error parsing timezone!");
+    /// for transition in tz.transitions(1640995200..1672531200) {
+    ///     println!("{} {}", transition.timestamp, transition.abbreviation);
+    /// }
+    /// ```
+    pub fn transitions(&self, range: Range<i64>) -> Transitions {
+        Transitions::new(self.clone(), range)
+    }
+
+    /// Returns the identifiers of all timezones in the bundled database, such as
+    /// "America/Chicago". Use [`TzDb`] to filter them by region or country.
+    pub fn identifiers() -> Vec<String> {
+        TzDb::builtin().identifiers()
+    }
+
+    /// Returns the `timelib_tzinfo` to hand to timelib, which only reads from it, or null if the
+    /// timezone isn't from a database.
+    pub(crate) fn tzi(&self) -> *mut timelib_tzinfo {
+        match &self.kind {
+            Kind::Id { tzi, .. } => tzi.as_ptr(),
+            _ => ptr::null_mut(),
+        }
+    }
+
+    /// Returns a new `timelib_time` holding the local time of the timestamp in this timezone,
+    /// which must be freed with `timelib_time_dtor`.
+    pub(crate) unsafe fn local_time(&self, timestamp: i64) -> *mut timelib_time {
+        let time = timelib_time_ctor();
+        self.apply_to(time);
+        timelib_unixtime2local(time, timestamp);
+        time
+    }
+
+    /// Sets the timezone of a `timelib_time`.
+    pub(crate) unsafe fn apply_to(&self, time: *mut timelib_time) {
+        match &self.kind {
+            Kind::Id { tzi, .. } => {
+                (*time).tz_info = tzi.as_ptr();
+                (*time).zone_type = TIMELIB_ZONETYPE_ID;
+            }
+            Kind::Offset(utc_offset) => {
+                timelib_set_timezone_from_offset(time, *utc_offset as timelib_sll);
+            }
+            Kind::Abbr {
+                abbreviation,
+                utc_offset,
+                is_dst,
+            } => {
+                // timelib keeps the DST hour out of the offset of abbreviations.
+                let dst = *is_dst as i32;
+                let abbr_info = timelib_abbr_info {
+                    utc_offset: (*utc_offset - dst * 3600) as timelib_sll,
+                    abbr: abbreviation.as_ptr() as *mut _,
+                    dst,
+                };
+                timelib_set_timezone_from_abbr(time, abbr_info);
+            }
+        }
+    }
+}
+
+/// Formats an offset in seconds east of UTC as e.g. "+05:30".
+fn format_offset(utc_offset: i32) -> String {
+    let sign = if utc_offset < 0 { '-' } else { '+' };
+    let seconds = utc_offset.unsigned_abs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if seconds == 0 {
+        format!("{sign}{hours:02}:{minutes:02}")
+    } else {
+        format!("{sign}{hours:02}:{minutes:02}:{seconds:02}")
+    }
+}
+
+/// Parses offsets such as "+05:30", "-0800", "+5" and "UTC+05:30:15" into seconds east of UTC.
+fn parse_offset_seconds(offset: &str) -> Option<i32> {
+    let offset = ["UTC", "GMT"]
+        .iter()
+        .find_map(|prefix| {
+            offset
+                .get(..prefix.len())
+                .filter(|p| p.eq_ignore_ascii_case(prefix))
+                .map(|_| &offset[prefix.len()..])
+        })
+        .unwrap_or(offset);
+    let (sign, rest) = match offset.as_bytes().first()? {
+        b'+' => (1, &offset[1..]),
+        b'-' => (-1, &offset[1..]),
+        _ => return None,
+    };
+    let parts: Vec<&str> = if rest.contains(':') {
+        rest.split(':').collect()
+    } else if rest.len() <= 2 {
+        vec![rest]
+    } else {
+        // "HHMM" or "HHMMSS"
+        let (hours, rest) = rest.split_at(rest.len().min(2));
+        let (minutes, seconds) = rest.split_at(rest.len().min(2));
+        [hours, minutes, seconds]
+            .into_iter()
+            .filter(|p| !p.is_empty())
+            .collect()
+    };
+    if parts.is_empty() || parts.len() > 3 {
+        return None;
+    }
+    let mut seconds = 0;
+    for (i, part) in parts.iter().enumerate() {
+        let valid_len = if i == 0 { 1..=2 } else { 2..=2 };
+        if !valid_len.contains(&part.len()) || !part.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let value: i32 = part.parse().ok()?;
+        if i > 0 && value >= 60 {
+            return None;
+        }
+        seconds = seconds * 60 + value;
+    }
+    Some(sign * seconds * 60i32.pow(3 - parts.len() as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+    use crate::{strtotime, tzif::tests::tzif};
+
+    #[test]
+    fn timezone_invalid_timezone() {
+        let result = Timezone::parse("pizza");
+        assert_eq!(Err(Error::Timezone(TzError::NoSuchTimezone)), result);
+    }
+
+    #[test]
+    fn timezone_invalid_timezone_string() {
+        let result = Timezone::parse("UTC\0");
+        assert_eq!(Err(Error::InteriorNul(3)), result);
+    }
+
+    #[test]
+    fn timezone_valid_timezone() {
+        let result = Timezone::parse("America/Chicago");
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn timezone_offset_at() {
+        let tz = Timezone::parse("America/Chicago").unwrap();
+        assert_eq!(
+            ZoneOffset {
+                utc_offset: -18000,
+                is_dst: true,
+                abbreviation: "CDT".into(),
+                transition_time: 1647158400,
+            },
+            tz.offset_at(1654318800)
+        );
+        assert_eq!(
+            ZoneOffset {
+                utc_offset: -21600,
+                is_dst: false,
+                abbreviation: "CST".into(),
+                transition_time: 1667718000,
+            },
+            tz.offset_at(1672552800)
+        );
+
+        let utc = Timezone::parse("UTC").unwrap().offset_at(1654318800);
+        assert_eq!((0, false), (utc.utc_offset, utc.is_dst));
+        assert_eq!("UTC (UTC+00:00)", utc.to_string());
+    }
+
+    #[test]
+    fn timezone_location() {
+        let tz = Timezone::parse("America/Chicago").unwrap();
+        assert_eq!("America/Chicago", tz.name());
+        assert_eq!(Some("US".to_string()), tz.country_code());
+        assert!((tz.latitude().unwrap() - 41.85).abs() < 0.001);
+        assert!((tz.longitude().unwrap() + 87.65).abs() < 0.001);
+        assert_eq!(Some("Central (most areas)".to_string()), tz.comments());
+
+        let utc = Timezone::parse("UTC").unwrap();
+        assert_eq!(None, utc.country_code());
+        assert_eq!(None, utc.latitude());
+        assert_eq!(None, utc.longitude());
+        assert_eq!(None, utc.comments());
+    }
+
+    #[test]
+    fn timezone_from_offset() {
+        let tz = Timezone::from_offset(19800);
+        assert_eq!("+05:30", tz.name());
+        assert_eq!(Ok(1654281000), strtotime("2022-06-04 00:00:00", None, &tz));
+        assert_eq!(Ok(1654367400), strtotime("tomorrow", Some(1654318800), &tz));
+        assert_eq!(
+            Ok(1654318800),
+            strtotime("2022-06-04 00:00:00 America/Chicago", None, &tz)
+        );
+        let offset = tz.offset_at(1654318800);
+        assert_eq!((19800, false), (offset.utc_offset, offset.is_dst));
+        assert_eq!(0, tz.transitions(0..i64::MAX).count());
+        assert_eq!(None, tz.country_code());
+    }
+
+    #[test]
+    fn timezone_parse_offset() {
+        for (input, expected) in [
+            ("+05:30", 19800),
+            ("+0530", 19800),
+            ("+5:30", 19800),
+            ("UTC+05:30", 19800),
+            ("gmt-8", -28800),
+            ("-08", -28800),
+            ("-0800", -28800),
+            ("+00:00", 0),
+            ("+01:02:03", 3723),
+            ("+010203", 3723),
+        ] {
+            assert_eq!(
+                Ok(Timezone::from_offset(expected)),
+                Timezone::parse_offset(input)
+            );
+        }
+        for input in [
+            "",
+            "05:30",
+            "+",
+            "+5:3",
+            "+05:60",
+            "+053",
+            "+05:30:00:00",
+            "+ab",
+        ] {
+            assert_eq!(
+                Err(Error::InvalidOffset(input.into())),
+                Timezone::parse_offset(input)
+            );
+        }
+        assert_eq!("-00:19:32", Timezone::from_offset(-1172).name());
+    }
+
+    #[test]
+    fn timezone_abbreviation() {
+        let pst = Timezone::parse_abbreviation("pst").unwrap();
+        assert_eq!("PST", pst.name());
+        assert_eq!(Ok(1654329600), strtotime("2022-06-04 00:00:00", None, &pst));
+
+        let cest = Timezone::from_abbreviation("CEST", 7200, true).unwrap();
+        assert_eq!(
+            Ok(1654293600),
+            strtotime("2022-06-04 00:00:00", None, &cest)
+        );
+        assert_eq!(
+            ZoneOffset {
+                utc_offset: 7200,
+                is_dst: true,
+                abbreviation: "CEST".into(),
+                transition_time: 0,
+            },
+            cest.offset_at(0)
+        );
+        assert_eq!(Ok(cest), Timezone::parse_abbreviation("CEST"));
+
+        let utc = Timezone::parse_abbreviation("UTC").unwrap();
+        assert_eq!(Ok(1654300800), strtotime("2022-06-04 00:00:00", None, &utc));
+        assert_eq!(
+            Err(Error::Timezone(TzError::NoSuchTimezone)),
+            Timezone::parse_abbreviation("NOPE")
+        );
+    }
+
+    #[test]
+    fn timezone_from_posix() {
+        let tz = Timezone::from_posix("EST5EDT,M3.2.0,M11.1.0").unwrap();
+        assert_eq!("EST5EDT,M3.2.0,M11.1.0", tz.name());
+        assert!(!tz.is_slim());
+        let summer = tz.offset_at(1654318800);
+        assert_eq!(
+            (-14400, true, "EDT"),
+            (summer.utc_offset, summer.is_dst, &*summer.abbreviation)
+        );
+        let winter = tz.offset_at(1672552800);
+        assert_eq!(
+            (-18000, false, "EST"),
+            (winter.utc_offset, winter.is_dst, &*winter.abbreviation)
+        );
+        assert_eq!(
+            vec![1647154800, 1667714400],
+            tz.transitions(1640995200..1672531200)
+                .map(|t| t.timestamp)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Ok(1654315200), strtotime("2022-06-04 00:00:00", None, &tz));
+
+        let tz = Timezone::from_posix("<+0330>-3:30").unwrap();
+        assert_eq!(12600, tz.offset_at(1654318800).utc_offset);
+        assert_eq!("+0330", tz.offset_at(1654318800).abbreviation);
+        assert_eq!(0, tz.transitions(0..4102444800).count());
+
+        assert_eq!(
+            Err(Error::Timezone(TzError::EmptyPosixString)),
+            Timezone::from_posix("")
+        );
+        assert_eq!(
+            Err(Error::Timezone(TzError::CorruptPosixString)),
+            Timezone::from_posix(",,,")
+        );
+    }
+
+    #[test]
+    fn timezone_identifiers() {
+        let identifiers = Timezone::identifiers();
+        assert!(identifiers.contains(&"Europe/Amsterdam".to_string()));
+        assert!(identifiers.iter().all(|id| Timezone::parse(id).is_ok()));
+    }
+
+    #[test]
+    fn timezone_db_version() {
+        assert_eq!("2026.1", Timezone::db_version());
+    }
+
+    #[test]
+    fn timezone_from_tzif_bytes() {
+        // America/Chicago since 2007, as a slim file.
+        let slim = tzif(
+            3,
+            &[(1167638400, 0)],
+            &[(-21600, false, "CST")],
+            "CST6CDT,M3.2.0,M11.1.0",
+        );
+        let tz = Timezone::from_tzif_bytes("Test/Chicago", &slim).unwrap();
+        assert!(tz.is_slim());
+        assert_eq!(Ok(1654318800), strtotime("jun 4 2022", None, &tz));
+        assert_eq!(Ok(1672552800), strtotime("jan 1 2023", None, &tz));
+        assert!(!Timezone::parse("America/Chicago").unwrap().is_slim());
+
+        let v1 = tzif(0, &[], &[(3600, false, "CET")], "");
+        let tz = Timezone::from_tzif_bytes("Test/Fixed", &v1).unwrap();
+        assert!(!tz.is_slim());
+        assert_eq!(Ok(1654293600), strtotime("jun 4 2022", None, &tz));
+
+        assert_eq!(
+            Err(Error::InvalidTzif("Test/Truncated".into())),
+            Timezone::from_tzif_bytes("Test/Truncated", &slim[..slim.len() - 1])
+        );
+    }
+
+    #[test]
+    fn timezone_clone() {
+        let tz = Timezone::parse("America/Chicago").unwrap();
+        let clone = tz.clone();
+        assert_eq!(tz, clone);
+        assert_ne!(tz, Timezone::parse("America/Chicago").unwrap());
+        drop(tz);
+        assert_eq!(Ok(1654318800), strtotime("jun 4 2022", None, &clone));
+    }
+
+    #[test]
+    fn timezone_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Timezone>();
+
+        let tz = Arc::new(Timezone::parse("America/Chicago").unwrap());
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let tz = Arc::clone(&tz);
+                thread::spawn(move || strtotime("jun 4 2022", None, &tz))
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(Ok(1654318800), handle.join().unwrap());
+        }
+    }
+}
@@ -0,0 +1,365 @@
+use std::{
+    ffi::{c_int, c_uint, CStr},
+    ops::Range,
+};
+
+use crate::{internal::*, Timezone};
+
+/// The first year to compute transitions for from a POSIX TZ string, so that unbounded ranges
+/// don't start a billion years in the past.
+const FIRST_RULE_YEAR: i64 = 1;
+
+/// Mirrors timelib's `ttinfo`, which is defined in `timelib_private.h` and therefore opaque to
+/// bindgen.
+#[repr(C)]
+struct TtInfo {
+    offset: i32,
+    isdst: c_int,
+    abbr_idx: c_uint,
+    _isstdcnt: c_uint,
+    _isgmtcnt: c_uint,
+}
+
+/// A change of UTC offset, DST flag or abbreviation in a timezone.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Transition {
+    /// When the change happens, in seconds since the epoch.
+    pub timestamp: i64,
+    /// Seconds east of UTC from `timestamp` onwards.
+    pub utc_offset: i32,
+    /// Whether daylight saving time is in effect from `timestamp` onwards.
+    pub is_dst: bool,
+    /// The abbreviation in use from `timestamp` onwards, e.g. "CDT".
+    pub abbreviation: String,
+}
+
+/// An iterator over the transitions of a timezone within a range of timestamps, created by
+/// [`Timezone::transitions`].
+///
+/// Transitions come from the timezone's transition table first, then from its POSIX TZ string
+/// for the years after the table ends, or for every year of the range if the timezone has no
+/// table.
+#[derive(Debug, Clone)]
+pub struct Transitions {
+    tz: Timezone,
+    range: Range<i64>,
+    /// The next entry of the transition table to yield.
+    index: usize,
+    /// The next year to compute transitions for from the POSIX TZ string.
+    year: i64,
+    /// Transitions computed from the POSIX TZ string that haven't been yielded yet.
+    pending: Vec<Transition>,
+}
+
+impl Transitions {
+    pub(crate) fn new(tz: Timezone, range: Range<i64>) -> Self {
+        let table = tzinfo(&tz).map_or(&[][..], table);
+        let index = table.partition_point(|&time| time < range.start);
+        let last = table.last().copied().unwrap_or(i64::MIN);
+        // A rule for one local year can take effect late in the previous UTC year.
+        let year = (year_of(last.max(range.start)) - 1).max(FIRST_RULE_YEAR);
+        Self {
+            tz,
+            range,
+            index,
+            year,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Computes the next year's transitions from the POSIX TZ string, returning false if there
+    /// are none to compute.
+    fn fill_pending(&mut self) -> bool {
+        let Some(tzi) = tzinfo(&self.tz) else {
+            return false;
+        };
+        if tzi.posix_info.is_null() {
+            return false;
+        }
+        let posix = unsafe { &*tzi.posix_info };
+        if posix.dst_begin.is_null() || posix.dst_end.is_null() {
+            return false;
+        }
+        let year_start = days_from_civil(self.year, 1, 1) * 86400;
+        if year_start >= self.range.end {
+            return false;
+        }
+        let (begin, end) = unsafe { (&*posix.dst_begin, &*posix.dst_end) };
+        let last = table(tzi).last().copied().unwrap_or(i64::MIN);
+        let name = |abbr: *mut std::os::raw::c_char| unsafe {
+            CStr::from_ptr(abbr).to_string_lossy().into_owned()
+        };
+        let mut transitions = vec![
+            Transition {
+                timestamp: year_start + rule_offset(begin, self.year) - posix.std_offset,
+                utc_offset: posix.dst_offset as i32,
+                is_dst: true,
+                abbreviation: name(posix.dst),
+            },
+            Transition {
+                timestamp: year_start + rule_offset(end, self.year) - posix.dst_offset,
+                utc_offset: posix.std_offset as i32,
+                is_dst: false,
+                abbreviation: name(posix.std),
+            },
+        ];
+        transitions.retain(|t| t.timestamp > last && self.range.contains(&t.timestamp));
+        // Popped from the back, so store the latest first.
+        transitions.sort_by_key(|t| std::cmp::Reverse(t.timestamp));
+        self.pending = transitions;
+        self.year += 1;
+        true
+    }
+}
+
+impl Iterator for Transitions {
+    type Item = Transition;
+
+    fn next(&mut self) -> Option<Transition> {
+        let tzi = tzinfo(&self.tz)?;
+        let table = table(tzi);
+        if let Some(&timestamp) = table.get(self.index) {
+            if timestamp >= self.range.end {
+                return None;
+            }
+            let (types, type_idx) = unsafe {
+                (
+                    std::slice::from_raw_parts(
+                        tzi.type_ as *const TtInfo,
+                        tzi.bit64.typecnt as usize,
+                    ),
+                    *tzi.trans_idx.add(self.index),
+                )
+            };
+            self.index += 1;
+            let ttinfo = &types[type_idx as usize];
+            let abbreviation =
+                unsafe { CStr::from_ptr(tzi.timezone_abbr.add(ttinfo.abbr_idx as usize)) };
+            return Some(Transition {
+                timestamp,
+                utc_offset: ttinfo.offset,
+                is_dst: ttinfo.isdst != 0,
+                abbreviation: abbreviation.to_string_lossy().into_owned(),
+            });
+        }
+        loop {
+            if let Some(transition) = self.pending.pop() {
+                return Some(transition);
+            }
+            if !self.fill_pending() {
+                return None;
+            }
+        }
+    }
+}
+
+/// Returns the timezone's `timelib_tzinfo`, if it is from a database.
+fn tzinfo(tz: &Timezone) -> Option<&timelib_tzinfo> {
+    unsafe { tz.tzi().as_ref() }
+}
+
+/// Returns the timestamps of the timezone's transition table.
+fn table(tzi: &timelib_tzinfo) -> &[i64] {
+    if tzi.trans.is_null() {
+        return &[];
+    }
+    unsafe { std::slice::from_raw_parts(tzi.trans, tzi.bit64.timecnt as usize) }
+}
+
+/// Returns the number of seconds from the start of `year` (local time) at which a POSIX TZ
+/// string rule takes effect.
+fn rule_offset(rule: &timelib_posix_trans_info, year: i64) -> i64 {
+    let leap = is_leap_year(year);
+    let day = match rule.type_ as u32 {
+        TIMELIB_POSIX_TRANS_TYPE_JULIAN_NO_FEB29 => {
+            // "Jn": 1-based, never counting February 29.
+            let day = unsafe { rule.__bindgen_anon_1.days as i64 } - 1;
+            if leap && day >= 59 {
+                day + 1
+            } else {
+                day
+            }
+        }
+        TIMELIB_POSIX_TRANS_TYPE_JULIAN_FEB29 => unsafe { rule.__bindgen_anon_1.days as i64 },
+        _ => {
+            // "Mm.w.d": day d (0 = Sunday) of week w (5 = last) of month m.
+            let mwd = unsafe { rule.__bindgen_anon_1.mwd };
+            let (month, week, dow) = (mwd.month as i64, mwd.week as i64, mwd.dow as i64);
+            let first = days_from_civil(year, month, 1);
+            // 1970-01-01 was a Thursday.
+            let first_dow = (first + 4).rem_euclid(7);
+            let mut day = 1 + (dow - first_dow).rem_euclid(7) + (week - 1) * 7;
+            while day > days_in_month(year, month) {
+                day -= 7;
+            }
+            first - days_from_civil(year, 1, 1) + day - 1
+        }
+    };
+    day * 86400 + rule.hour as i64
+}
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the number of days between 1970-01-01 and the given date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Returns the year (UTC) the timestamp falls in.
+fn year_of(timestamp: i64) -> i64 {
+    let days = timestamp.div_euclid(86400);
+    // Start from an underestimate of the year and count up.
+    let days_per_year = if days < 0 { 365 } else { 366 };
+    let mut year = 1970
+        + days
+            .div_euclid(days_per_year)
+            .clamp(-1_000_000_000, 1_000_000_000);
+    while days_from_civil(year + 1, 1, 1) <= days {
+        year += 1;
+    }
+    year
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tzif::tests::tzif;
+
+    fn transition(timestamp: i64, utc_offset: i32, is_dst: bool, abbr: &str) -> Transition {
+        Transition {
+            timestamp,
+            utc_offset,
+            is_dst,
+            abbreviation: abbr.into(),
+        }
+    }
+
+    #[test]
+    fn transitions_from_table() {
+        let tz = Timezone::parse("America/Chicago").unwrap();
+        let transitions: Vec<_> = tz.transitions(1640995200..1672531200).collect();
+        assert_eq!(
+            vec![
+                transition(1647158400, -18000, true, "CDT"),
+                transition(1667718000, -21600, false, "CST"),
+            ],
+            transitions
+        );
+        assert_eq!(0, tz.transitions(1647158401..1667718000).count());
+    }
+
+    #[test]
+    fn transitions_from_posix_string() {
+        // America/Chicago since 2007, as a slim file.
+        let slim = tzif(
+            3,
+            &[(1167638400, 0)],
+            &[(-21600, false, "CST")],
+            "CST6CDT,M3.2.0,M11.1.0",
+        );
+        let tz = Timezone::from_tzif_bytes("Test/Chicago", &slim).unwrap();
+        let transitions: Vec<_> = tz.transitions(1640995200..1704067200).collect();
+        assert_eq!(
+            vec![
+                transition(1647158400, -18000, true, "CDT"),
+                transition(1667718000, -21600, false, "CST"),
+                transition(1678608000, -18000, true, "CDT"),
+                transition(1699167600, -21600, false, "CST"),
+            ],
+            transitions
+        );
+
+        let southern = tzif(
+            2,
+            &[(0, 0)],
+            &[(36000, false, "AEST")],
+            "AEST-10AEDT,M10.1.0,M4.1.0/3",
+        );
+        let tz = Timezone::from_tzif_bytes("Test/Sydney", &southern).unwrap();
+        let transitions: Vec<_> = tz.transitions(1704067200..1735689600).collect();
+        assert_eq!(
+            vec![
+                transition(1712419200, 36000, false, "AEST"),
+                transition(1728144000, 39600, true, "AEDT"),
+            ],
+            transitions
+        );
+    }
+
+    #[test]
+    fn transitions_from_posix_without_table() {
+        let tz = Timezone::from_posix("EST5EDT,M3.2.0,M11.1.0").unwrap();
+        let transitions: Vec<_> = tz.transitions(-31536000..31536000).collect();
+        assert_eq!(
+            vec![
+                transition(-25722000, -14400, true, "EDT"),
+                transition(-5162400, -18000, false, "EST"),
+                transition(5727600, -14400, true, "EDT"),
+                transition(26287200, -18000, false, "EST"),
+            ],
+            transitions
+        );
+
+        let transitions: Vec<_> = tz.transitions(i64::MIN..31536000).collect();
+        assert_eq!(2 * 1970, transitions.len());
+        assert_eq!(
+            transition(-62129610000, -14400, true, "EDT"),
+            transitions[0]
+        );
+        assert_eq!(
+            transition(26287200, -18000, false, "EST"),
+            transitions[2 * 1970 - 1]
+        );
+    }
+
+    #[test]
+    fn transitions_fixed_offset() {
+        let tz = Timezone::from_tzif_bytes(
+            "Test/Fixed",
+            &tzif(2, &[], &[(3600, false, "+01")], "<+01>-1"),
+        )
+        .unwrap();
+        assert_eq!(0, tz.transitions(0..4102444800).count());
+    }
+
+    #[test]
+    fn transitions_julian_rules() {
+        let rule = |type_: u32, days: c_int, hour: c_int| timelib_posix_trans_info {
+            type_: type_ as c_int,
+            __bindgen_anon_1: _timelib_posix_trans_info__bindgen_ty_1 { days },
+            hour,
+        };
+        // J60 is always March 1st; 59 is February 29th in leap years.
+        let j60 = rule(TIMELIB_POSIX_TRANS_TYPE_JULIAN_NO_FEB29, 60, 7200);
+        assert_eq!(60 * 86400 + 7200, rule_offset(&j60, 2024));
+        assert_eq!(59 * 86400 + 7200, rule_offset(&j60, 2023));
+        let n59 = rule(TIMELIB_POSIX_TRANS_TYPE_JULIAN_FEB29, 59, 0);
+        assert_eq!(59 * 86400, rule_offset(&n59, 2024));
+    }
+
+    #[test]
+    fn transitions_civil_dates() {
+        assert_eq!(0, days_from_civil(1970, 1, 1));
+        assert_eq!(19723, days_from_civil(2024, 1, 1));
+        assert_eq!(-719528, days_from_civil(0, 1, 1));
+        assert_eq!(2024, year_of(1704067200));
+        assert_eq!(2023, year_of(1704067199));
+        assert_eq!(1969, year_of(-1));
+    }
+}
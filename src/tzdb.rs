@@ -0,0 +1,391 @@
+use std::{
+    collections::BTreeMap,
+    ffi::{CStr, CString},
+    path::Path,
+    sync::Arc,
+};
+
+use crate::{cache, internal::*, tzif, Error, Timezone};
+
+/// A timezone database, either the one bundled with timelib or one loaded at runtime.
+///
+/// Cloning is cheap: clones share the same underlying database.
+#[derive(Debug, Clone)]
+pub struct TzDb {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    db: *const timelib_tzdb,
+    kind: Kind,
+}
+
+#[derive(Debug)]
+enum Kind {
+    Builtin,
+    Zoneinfo,
+    /// Owns the buffers `Inner::db` points into.
+    Memory {
+        _buffers: Box<MemoryDb>,
+    },
+}
+
+/// A `timelib_tzdb` along with the buffers it points into.
+#[derive(Debug)]
+struct MemoryDb {
+    tzdb: timelib_tzdb,
+    _version: CString,
+    _ids: Vec<CString>,
+    _index: Vec<timelib_tzdb_index_entry>,
+    _data: Vec<u8>,
+}
+
+// timelib never mutates a database after creating it, so it can be read from any thread.
+unsafe impl Send for Inner {}
+unsafe impl Sync for Inner {}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        match self.kind {
+            Kind::Builtin => {}
+            Kind::Zoneinfo => {
+                cache::remove_db(self.db);
+                unsafe {
+                    timelib_zoneinfo_dtor(self.db as *mut timelib_tzdb);
+                }
+            }
+            Kind::Memory { .. } => cache::remove_db(self.db),
+        }
+    }
+}
+
+impl TzDb {
+    /// Returns the timezone database bundled with timelib.
+    pub fn builtin() -> TzDb {
+        Self {
+            inner: Arc::new(Inner {
+                db: unsafe { timelib_builtin_db() },
+                kind: Kind::Builtin,
+            }),
+        }
+    }
+
+    /// Loads a timezone database from a zoneinfo directory, such as `/usr/share/zoneinfo`.
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - The directory containing the TZif files.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// let db = timelib::TzDb::open("/usr/share/zoneinfo").expect("Error loading database!");
+    /// let tz = timelib::Timezone::parse_with_db("America/Chicago", &db);
+    /// ```
+    pub fn open(directory: impl AsRef<Path>) -> Result<TzDb, Error> {
+        let directory = directory.as_ref();
+        let invalid = || Error::InvalidDatabase(directory.to_path_buf());
+        let dir_c_str = directory
+            .to_str()
+            .and_then(|dir| CString::new(dir).ok())
+            .ok_or_else(invalid)?;
+        if !directory.is_dir() {
+            return Err(invalid());
+        }
+        let db = unsafe { timelib_zoneinfo(dir_c_str.as_ptr()) };
+        if db.is_null() {
+            return Err(invalid());
+        }
+        Ok(Self {
+            inner: Arc::new(Inner {
+                db,
+                kind: Kind::Zoneinfo,
+            }),
+        })
+    }
+
+    /// Builds a timezone database from in-memory TZif data (versions 1 to 4), such as the
+    /// contents of a zoneinfo directory fetched from elsewhere.
+    ///
+    /// Names are looked up case-insensitively, like in the other databases, so they must not
+    /// differ only by case.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The version to report for the database, e.g. "2024a".
+    /// * `zones` - The timezone names along with their TZif data.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// let utc = std::fs::read("/usr/share/zoneinfo/UTC").unwrap();
+    /// let db = timelib::TzDb::from_tzif("2024a", [("UTC", utc)]).expect("Error loading database!");
+    /// let tz = timelib::Timezone::parse_with_db("UTC", &db);
+    /// ```
+    pub fn from_tzif<N, B>(
+        version: &str,
+        zones: impl IntoIterator<Item = (N, B)>,
+    ) -> Result<TzDb, Error>
+    where
+        N: AsRef<str>,
+        B: AsRef<[u8]>,
+    {
+        let version = CString::new(version).map_err(|e| Error::InteriorNul(e.nul_position()))?;
+        // timelib binary searches the index using a case-insensitive comparison.
+        let mut sorted = BTreeMap::new();
+        for (name, data) in zones {
+            let name = name.as_ref();
+            let id = CString::new(name).map_err(|e| Error::InteriorNul(e.nul_position()))?;
+            let data =
+                tzif::normalize(data.as_ref()).ok_or_else(|| Error::InvalidTzif(name.into()))?;
+            if sorted
+                .insert(name.to_ascii_lowercase(), (id, data))
+                .is_some()
+            {
+                return Err(Error::DuplicateTimezone(name.into()));
+            }
+        }
+        if sorted.is_empty() {
+            return Err(Error::EmptyInput);
+        }
+
+        let mut ids = Vec::with_capacity(sorted.len());
+        let mut positions = Vec::with_capacity(sorted.len());
+        let mut data = Vec::new();
+        for (id, zone) in sorted.into_values() {
+            let pos = u32::try_from(data.len())
+                .map_err(|_| Error::InvalidTzif(id.to_string_lossy().into_owned()))?;
+            positions.push(pos);
+            ids.push(id);
+            data.extend_from_slice(&zone);
+        }
+        let index: Vec<_> = ids
+            .iter()
+            .zip(positions)
+            .map(|(id, pos)| timelib_tzdb_index_entry {
+                id: id.as_ptr() as *mut _,
+                pos,
+            })
+            .collect();
+        let mut memory = Box::new(MemoryDb {
+            tzdb: timelib_tzdb {
+                version: version.as_ptr(),
+                index_size: index.len() as i32,
+                index: index.as_ptr(),
+                data: data.as_ptr(),
+            },
+            _version: version,
+            _ids: ids,
+            _index: index,
+            _data: data,
+        });
+        Ok(Self {
+            inner: Arc::new(Inner {
+                db: &mut memory.tzdb,
+                kind: Kind::Memory { _buffers: memory },
+            }),
+        })
+    }
+
+    /// Returns the version of the timezone database.
+    pub fn version(&self) -> String {
+        let cstr = unsafe { CStr::from_ptr((*self.inner.db).version) };
+        String::from_utf8_lossy(cstr.to_bytes()).to_string()
+    }
+
+    /// Returns the identifiers of all timezones in the database, such as "America/Chicago".
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let identifiers = timelib::TzDb::builtin().identifiers();
+    /// assert!(identifiers.contains(&"America/Chicago".to_string()));
+    /// ```
+    pub fn identifiers(&self) -> Vec<String> {
+        self.ids()
+            .map(|id| id.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    /// Returns the identifiers of the timezones whose name starts with `prefix`, e.g. "America/".
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let identifiers = timelib::TzDb::builtin().identifiers_with_prefix("America/");
+    /// assert!(identifiers.contains(&"America/Chicago".to_string()));
+    /// ```
+    pub fn identifiers_with_prefix(&self, prefix: &str) -> Vec<String> {
+        self.identifiers()
+            .into_iter()
+            .filter(|id| id.starts_with(prefix))
+            .collect()
+    }
+
+    /// Returns the identifiers of the timezones located in the country with the given ISO 3166-1
+    /// alpha-2 code, e.g. "US".
+    ///
+    /// Only the bundled database records countries, so this is empty for databases loaded from
+    /// TZif data. Every timezone is loaded to look up its country, which makes this call slow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let identifiers = timelib::TzDb::builtin().identifiers_for_country("US");
+    /// assert!(identifiers.contains(&"America/Chicago".to_string()));
+    /// ```
+    pub fn identifiers_for_country(&self, country_code: &str) -> Vec<String> {
+        self.identifiers()
+            .into_iter()
+            .filter(|id| {
+                Timezone::parse_with_db(id, self)
+                    .ok()
+                    .and_then(|tz| tz.country_code())
+                    .is_some_and(|code| code.eq_ignore_ascii_case(country_code))
+            })
+            .collect()
+    }
+
+    /// Walks the database's index.
+    fn ids(&self) -> impl Iterator<Item = &CStr> {
+        let db = unsafe { &*self.inner.db };
+        let index = if db.index.is_null() || db.index_size <= 0 {
+            &[][..]
+        } else {
+            unsafe { std::slice::from_raw_parts(db.index, db.index_size as usize) }
+        };
+        index
+            .iter()
+            .map(|entry| unsafe { CStr::from_ptr(entry.id) })
+    }
+
+    /// Returns the `timelib_tzdb` to hand to timelib, which only reads from it.
+    pub(crate) fn as_ptr(&self) -> *const timelib_tzdb {
+        self.inner.db
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{strtotime, strtotime_with_db, tzif::tests::tzif, TzError};
+
+    const ZONEINFO: &str = "/usr/share/zoneinfo";
+
+    #[test]
+    fn tzdb_builtin_version() {
+        assert_eq!(Timezone::db_version(), TzDb::builtin().version());
+    }
+
+    #[test]
+    fn tzdb_identifiers() {
+        let db = TzDb::builtin();
+        let all = db.identifiers();
+        assert!(all.contains(&"America/Chicago".to_string()));
+        assert!(all.contains(&"UTC".to_string()));
+
+        let america = db.identifiers_with_prefix("America/");
+        assert!(america.contains(&"America/Chicago".to_string()));
+        assert!(america.iter().all(|id| id.starts_with("America/")));
+        assert!(america.len() < all.len());
+
+        let us = db.identifiers_for_country("us");
+        assert!(us.contains(&"America/Chicago".to_string()));
+        assert!(us.contains(&"Pacific/Honolulu".to_string()));
+        assert!(!us.contains(&"Europe/London".to_string()));
+        assert!(db
+            .identifiers_for_country("GB")
+            .contains(&"Europe/London".to_string()));
+        assert!(db.identifiers_for_country("XX").is_empty());
+    }
+
+    #[test]
+    fn tzdb_open_missing_directory() {
+        let result = TzDb::open("/does/not/exist");
+        assert_eq!(
+            Err(Error::InvalidDatabase("/does/not/exist".into())),
+            result.map(|_| ())
+        );
+    }
+
+    #[test]
+    fn tzdb_open_zoneinfo() {
+        if !Path::new(ZONEINFO).is_dir() {
+            return;
+        }
+        let db = TzDb::open(ZONEINFO).unwrap();
+        let tz = Timezone::parse_with_db("America/Chicago", &db).unwrap();
+        assert_eq!(Ok(1654318800), strtotime("jun 4 2022", None, &tz));
+        let utc = Timezone::parse_with_db("UTC", &db).unwrap();
+        assert_eq!(
+            Ok(1147453200),
+            strtotime_with_db("2006-05-12 13:00:00 America/New_York", None, &utc, &db)
+        );
+        assert_eq!(
+            Err(Error::Timezone(TzError::NoSuchTimezone)),
+            Timezone::parse_with_db("Not/A_Zone", &db).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn tzdb_from_tzif() {
+        let db = TzDb::from_tzif(
+            "test.1",
+            [
+                (
+                    "Test/Plus_Two",
+                    tzif(2, &[], &[(7200, false, "+02")], "<+02>-2"),
+                ),
+                (
+                    "Test/Minus_Five",
+                    tzif(0, &[], &[(-18000, false, "EST")], ""),
+                ),
+            ],
+        )
+        .unwrap();
+        assert_eq!("test.1", db.version());
+        assert_eq!(vec!["Test/Minus_Five", "Test/Plus_Two"], db.identifiers());
+
+        let tz = Timezone::parse_with_db("test/plus_two", &db).unwrap();
+        assert_eq!(Ok(1654293600), strtotime("jun 4 2022", None, &tz));
+        let tz = Timezone::parse_with_db("Test/Minus_Five", &db).unwrap();
+        assert_eq!(Ok(1654318800), strtotime("jun 4 2022", None, &tz));
+        assert_eq!(
+            Ok(1147431600),
+            strtotime_with_db("2006-05-12 13:00:00 Test/Plus_Two", None, &tz, &db)
+        );
+        assert_eq!(
+            Err(Error::Timezone(TzError::NoSuchTimezone)),
+            Timezone::parse_with_db("America/Chicago", &db).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn tzdb_from_tzif_invalid() {
+        assert_eq!(
+            Err(Error::InvalidTzif("Bad/Zone".into())),
+            TzDb::from_tzif("1", [("Bad/Zone", b"TZif2")]).map(|_| ())
+        );
+        assert_eq!(
+            Err(Error::EmptyInput),
+            TzDb::from_tzif("1", Vec::<(&str, &[u8])>::new()).map(|_| ())
+        );
+        let utc = tzif(0, &[], &[(0, false, "UTC")], "");
+        assert_eq!(
+            Err(Error::DuplicateTimezone("utc".into())),
+            TzDb::from_tzif("1", [("UTC", &utc), ("utc", &utc)]).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn tzdb_timezone_outlives_database() {
+        if !Path::new(ZONEINFO).is_dir() {
+            return;
+        }
+        let db = TzDb::open(ZONEINFO).unwrap();
+        let tz = Timezone::parse_with_db("Europe/London", &db).unwrap();
+        drop(db);
+        assert_eq!(Ok(1147435200), strtotime("2006-05-12 13:00:00", None, &tz));
+    }
+}
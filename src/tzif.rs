@@ -0,0 +1,308 @@
+//! Validation of TZif data (RFC 8536) before it is handed to timelib.
+//!
+//! timelib trusts the data in its databases and reads past the end of truncated or corrupt files,
+//! so every TZif blob loaded from memory is walked here first. Version 1 files, which timelib
+//! refuses, are upgraded to version 2 along the way.
+
+const MAGIC: &[u8] = b"TZif";
+const HEADER_LEN: usize = 44;
+
+/// The counts from a TZif header, in file order.
+#[derive(Clone, Copy)]
+struct Counts {
+    isutcnt: usize,
+    isstdcnt: usize,
+    leapcnt: usize,
+    timecnt: usize,
+    typecnt: usize,
+    charcnt: usize,
+}
+
+impl Counts {
+    /// Returns the length of the data block following the header.
+    fn block_len(&self, time_size: usize) -> usize {
+        self.timecnt * time_size
+            + self.timecnt
+            + self.typecnt * 6
+            + self.charcnt
+            + self.leapcnt * (time_size + 4)
+            + self.isstdcnt
+            + self.isutcnt
+    }
+}
+
+/// Reads the header at the start of `data`, returning the version byte and the counts.
+fn read_header(data: &[u8]) -> Option<(u8, Counts)> {
+    if data.len() < HEADER_LEN || &data[..4] != MAGIC {
+        return None;
+    }
+    let count = |i: usize| {
+        let start = 20 + i * 4;
+        u32::from_be_bytes(data[start..start + 4].try_into().unwrap()) as usize
+    };
+    Some((
+        data[4],
+        Counts {
+            isutcnt: count(0),
+            isstdcnt: count(1),
+            leapcnt: count(2),
+            timecnt: count(3),
+            typecnt: count(4),
+            charcnt: count(5),
+        },
+    ))
+}
+
+/// Checks that a data block only refers to types and abbreviations that exist.
+fn check_block(block: &[u8], counts: &Counts, time_size: usize) -> Option<()> {
+    if counts.typecnt == 0
+        || counts.charcnt == 0
+        || (counts.isstdcnt != 0 && counts.isstdcnt != counts.typecnt)
+        || (counts.isutcnt != 0 && counts.isutcnt != counts.typecnt)
+    {
+        return None;
+    }
+    let indices = &block[counts.timecnt * time_size..][..counts.timecnt];
+    if indices.iter().any(|&i| i as usize >= counts.typecnt) {
+        return None;
+    }
+    let types = &block[counts.timecnt * (time_size + 1)..][..counts.typecnt * 6];
+    if types.chunks(6).any(|t| t[5] as usize >= counts.charcnt) {
+        return None;
+    }
+    let chars = &block[counts.timecnt * (time_size + 1) + counts.typecnt * 6..][..counts.charcnt];
+    (chars.last() == Some(&0)).then_some(())
+}
+
+/// Validates TZif data of any version, returning it in a form timelib can read: version 2, 3
+/// and 4 files are returned unchanged and version 1 files are upgraded to version 2.
+///
+/// Returns `None` if the data is not valid TZif.
+pub(crate) fn normalize(data: &[u8]) -> Option<Vec<u8>> {
+    let (version, v1) = read_header(data)?;
+    let v1_end = HEADER_LEN.checked_add(v1.block_len(4))?;
+    if data.len() < v1_end {
+        return None;
+    }
+    match version {
+        0 => {
+            check_block(&data[HEADER_LEN..v1_end], &v1, 4)?;
+            Some(upgrade_v1(&data[..v1_end], &v1))
+        }
+        b'2'..=b'4' => {
+            let (_, v2) = read_header(&data[v1_end..])?;
+            let block_start = v1_end + HEADER_LEN;
+            let block_end = block_start.checked_add(v2.block_len(8))?;
+            if data.len() < block_end {
+                return None;
+            }
+            check_block(&data[block_start..block_end], &v2, 8)?;
+            // The footer holds a POSIX TZ string between two newlines.
+            let footer = data[block_end..].strip_prefix(b"\n")?;
+            let footer_len = footer.iter().position(|&b| b == b'\n')?;
+            Some(data[..block_end + footer_len + 2].to_vec())
+        }
+        _ => None,
+    }
+}
+
+/// Rewrites a validated version 1 file as version 2, with a footer that keeps the last local
+/// time type in effect forever.
+fn upgrade_v1(data: &[u8], counts: &Counts) -> Vec<u8> {
+    let body = &data[HEADER_LEN..];
+    let mut out = Vec::with_capacity(data.len() * 3);
+    out.extend_from_slice(data);
+    out[4] = b'2';
+    out.extend_from_within(..HEADER_LEN);
+
+    let (times, rest) = body.split_at(counts.timecnt * 4);
+    for time in times.chunks(4) {
+        let time = i32::from_be_bytes(time.try_into().unwrap()) as i64;
+        out.extend_from_slice(&time.to_be_bytes());
+    }
+    let (indices, rest) = rest.split_at(counts.timecnt);
+    let (types, rest) = rest.split_at(counts.typecnt * 6);
+    let (chars, rest) = rest.split_at(counts.charcnt);
+    let (leaps, rest) = rest.split_at(counts.leapcnt * 8);
+    out.extend_from_slice(indices);
+    out.extend_from_slice(types);
+    out.extend_from_slice(chars);
+    for leap in leaps.chunks(8) {
+        let time = i32::from_be_bytes(leap[..4].try_into().unwrap()) as i64;
+        out.extend_from_slice(&time.to_be_bytes());
+        out.extend_from_slice(&leap[4..]);
+    }
+    out.extend_from_slice(rest);
+
+    let last = indices.last().map_or(0, |&i| i as usize);
+    let ttinfo = &types[last * 6..][..6];
+    let utoff = i32::from_be_bytes(ttinfo[..4].try_into().unwrap());
+    let abbr = &chars[ttinfo[5] as usize..];
+    let abbr = &abbr[..abbr.iter().position(|&b| b == 0).unwrap_or(abbr.len())];
+    out.push(b'\n');
+    out.push(b'<');
+    out.extend_from_slice(abbr);
+    out.push(b'>');
+    out.extend_from_slice(posix_offset(utoff).as_bytes());
+    out.push(b'\n');
+    out
+}
+
+/// Builds a version 2 file without transitions, whose local time types are given as
+/// `(utc_offset, is_dst, abbreviation)` and which defers to the POSIX TZ string `footer`.
+pub(crate) fn without_transitions(types: &[(i32, bool, &[u8])], footer: &[u8]) -> Vec<u8> {
+    let mut block = Vec::new();
+    let mut chars = Vec::new();
+    for &(utoff, isdst, abbr) in types {
+        block.extend_from_slice(&utoff.to_be_bytes());
+        block.push(isdst as u8);
+        block.push(chars.len() as u8);
+        chars.extend_from_slice(abbr);
+        chars.push(0);
+    }
+    block.extend_from_slice(&chars);
+
+    let mut header = MAGIC.to_vec();
+    header.push(b'2');
+    header.extend_from_slice(&[0; 15]);
+    for count in [0, 0, 0, 0, types.len(), chars.len()] {
+        header.extend_from_slice(&(count as u32).to_be_bytes());
+    }
+
+    // Without transitions, the 32-bit and 64-bit blocks are identical.
+    let mut out = Vec::new();
+    for _ in 0..2 {
+        out.extend_from_slice(&header);
+        out.extend_from_slice(&block);
+    }
+    out.push(b'\n');
+    out.extend_from_slice(footer);
+    out.push(b'\n');
+    out
+}
+
+/// Formats a UTC offset the way POSIX TZ strings expect it, i.e. with the sign inverted.
+fn posix_offset(utoff: i32) -> String {
+    let sign = if utoff > 0 { "-" } else { "" };
+    let seconds = utoff.unsigned_abs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    match (minutes, seconds) {
+        (0, 0) => format!("{sign}{hours}"),
+        (_, 0) => format!("{sign}{hours}:{minutes:02}"),
+        _ => format!("{sign}{hours}:{minutes:02}:{seconds:02}"),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Builds a TZif file. `version` 0 produces a version 1 file; any other version writes the
+    /// data into the 64-bit block only, like a "slim" file.
+    pub(crate) fn tzif(
+        version: u8,
+        transitions: &[(i64, u8)],
+        types: &[(i32, bool, &str)],
+        footer: &str,
+    ) -> Vec<u8> {
+        let mut chars = Vec::new();
+        let mut ttinfos = Vec::new();
+        for &(utoff, isdst, abbr) in types {
+            ttinfos.extend_from_slice(&utoff.to_be_bytes());
+            ttinfos.push(isdst as u8);
+            ttinfos.push(chars.len() as u8);
+            chars.extend_from_slice(abbr.as_bytes());
+            chars.push(0);
+        }
+        let header = |version: u8, timecnt: usize| {
+            let mut header = MAGIC.to_vec();
+            header.push(version);
+            header.extend_from_slice(&[0; 15]);
+            for count in [0, 0, 0, timecnt, types.len(), chars.len()] {
+                header.extend_from_slice(&(count as u32).to_be_bytes());
+            }
+            header
+        };
+
+        let mut out = header(version, if version == 0 { transitions.len() } else { 0 });
+        let time_size = if version == 0 { 4 } else { 8 };
+        if version != 0 {
+            // A slim file's 32-bit block only has the mandatory type and abbreviation.
+            out[36..44].copy_from_slice(&[0, 0, 0, 1, 0, 0, 0, 1]);
+            out.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0]);
+            out.extend_from_slice(&header(version, transitions.len()));
+        }
+        for &(time, _) in transitions {
+            out.extend_from_slice(&time.to_be_bytes()[8 - time_size..]);
+        }
+        out.extend(transitions.iter().map(|&(_, index)| index));
+        out.extend_from_slice(&ttinfos);
+        out.extend_from_slice(&chars);
+        if version != 0 {
+            out.push(b'\n');
+            out.extend_from_slice(footer.as_bytes());
+            out.push(b'\n');
+        }
+        out
+    }
+
+    #[test]
+    fn tzif_normalize_v1() {
+        let v1 = tzif(
+            0,
+            &[(-100, 1), (100, 0)],
+            &[(19800, false, "+0530"), (3600, true, "CEST")],
+            "",
+        );
+        let v2 = normalize(&v1).unwrap();
+        assert_eq!(b'2', v2[4]);
+        assert_eq!(&v1[4 + 1..HEADER_LEN], &v2[4 + 1..HEADER_LEN]);
+        assert!(v2.ends_with(b"\n<+0530>-5:30\n"));
+        assert_eq!(Some(&v2[..]), normalize(&v2).as_deref());
+    }
+
+    #[test]
+    fn tzif_normalize_v2() {
+        let data = tzif(2, &[(0, 0)], &[(-18000, false, "EST")], "EST5");
+        let mut padded = data.clone();
+        padded.extend_from_slice(b"trailing");
+        assert_eq!(Some(data), normalize(&padded));
+    }
+
+    #[test]
+    fn tzif_normalize_invalid() {
+        assert_eq!(None, normalize(b""));
+        assert_eq!(None, normalize(b"PHP2 is not TZif"));
+        let data = tzif(3, &[(0, 0)], &[(0, false, "UTC")], "UTC0");
+        assert_eq!(None, normalize(&data[..data.len() - 1]));
+        assert_eq!(None, normalize(&data[..60]));
+        let bad_index = tzif(3, &[(0, 1)], &[(0, false, "UTC")], "UTC0");
+        assert_eq!(None, normalize(&bad_index));
+        let mut bad_version = data.clone();
+        bad_version[4] = b'5';
+        assert_eq!(None, normalize(&bad_version));
+    }
+
+    #[test]
+    fn tzif_without_transitions() {
+        let data = without_transitions(
+            &[(-18000, false, b"EST"), (-14400, true, b"EDT")],
+            b"EST5EDT,M3.2.0,M11.1.0",
+        );
+        let (version, counts) = read_header(&data).unwrap();
+        assert_eq!(
+            (b'2', 0, 2, 8),
+            (version, counts.timecnt, counts.typecnt, counts.charcnt)
+        );
+        assert!(data.ends_with(b"\nEST5EDT,M3.2.0,M11.1.0\n"));
+        assert_eq!(Some(&data[..]), normalize(&data).as_deref());
+    }
+
+    #[test]
+    fn tzif_posix_offset() {
+        assert_eq!("0", posix_offset(0));
+        assert_eq!("5", posix_offset(-18000));
+        assert_eq!("-5:30", posix_offset(19800));
+        assert_eq!("-0:19:32", posix_offset(1172));
+    }
+}
@@ -0,0 +1,70 @@
+use std::{ffi::CStr, fmt};
+
+use crate::internal::*;
+
+/// The UTC offset, DST flag and abbreviation in effect in a timezone at a given instant.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ZoneOffset {
+    /// Seconds east of UTC, e.g. -18000 for CDT.
+    pub utc_offset: i32,
+    /// Whether daylight saving time is in effect.
+    pub is_dst: bool,
+    /// The abbreviation, e.g. "CDT".
+    pub abbreviation: String,
+    /// The timestamp of the transition that put this offset in effect, or 0 if there was none.
+    pub transition_time: i64,
+}
+
+impl ZoneOffset {
+    /// Copies the fields of a `timelib_time_offset`.
+    pub(crate) unsafe fn from_raw(offset: &timelib_time_offset) -> Self {
+        Self {
+            utc_offset: offset.offset,
+            is_dst: offset.is_dst != 0,
+            abbreviation: if offset.abbr.is_null() {
+                String::new()
+            } else {
+                CStr::from_ptr(offset.abbr).to_string_lossy().into_owned()
+            },
+            transition_time: offset.transition_time,
+        }
+    }
+}
+
+impl fmt::Display for ZoneOffset {
+    /// Formats the offset as e.g. "CDT (UTC-05:00)".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.utc_offset < 0 { '-' } else { '+' };
+        let seconds = self.utc_offset.unsigned_abs();
+        write!(
+            f,
+            "{} (UTC{sign}{:02}:{:02})",
+            self.abbreviation,
+            seconds / 3600,
+            seconds / 60 % 60
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zone_offset_display() {
+        let offset = ZoneOffset {
+            utc_offset: -18000,
+            is_dst: true,
+            abbreviation: "CDT".into(),
+            transition_time: 1647158400,
+        };
+        assert_eq!("CDT (UTC-05:00)", offset.to_string());
+        let offset = ZoneOffset {
+            utc_offset: 19800,
+            is_dst: false,
+            abbreviation: "IST".into(),
+            transition_time: 0,
+        };
+        assert_eq!("IST (UTC+05:30)", offset.to_string());
+    }
+}